@@ -0,0 +1,395 @@
+use crate::common::{
+    find_solution, json_string, run_isolated, run_parts, RunError, RunOptions, RunReport,
+    DEFAULT_YEAR,
+};
+use crate::solution::Key;
+use crate::utils::lines_from_str;
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::Path;
+
+/// The file known-good answers are read from when none is specified.
+pub const DEFAULT_ANSWERS_PATH: &str = "answers.toml";
+
+/// The known answers to a day's puzzle for a single input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub input: String,
+    pub key: Key,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Entry {
+    #[must_use]
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Known-good answers, read from a small subset of TOML with one table per
+/// input file:
+///
+/// ```toml
+/// ["inputs/2024/day01.txt"]
+/// year = 2024 # optional
+/// day = 1
+/// part1 = "2176849"
+/// part2 = "23384288"
+/// ```
+///
+/// Parts whose answer is not known yet are simply left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    entries: Vec<Entry>,
+}
+
+/// Input paths are compared without any leading `./`.
+fn normalize(input: &str) -> &str {
+    input.trim_start_matches("./")
+}
+
+/// Parses a TOML basic string, outputting its contents and whatever follows
+/// the closing quote.
+fn parse_string(s: &str) -> Result<(String, &str), String> {
+    let mut chars = s
+        .strip_prefix('"')
+        .ok_or("expected a string")?
+        .char_indices();
+    let mut output = String::new();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((output, &s[i + 2..])),
+            '\\' => match chars.next() {
+                Some((_, '"')) => output.push('"'),
+                Some((_, '\\')) => output.push('\\'),
+                Some((_, 'n')) => output.push('\n'),
+                Some((_, 't')) => output.push('\t'),
+                _ => return Err("unsupported escape sequence".to_string()),
+            },
+            c => output.push(c),
+        }
+    }
+
+    Err("unterminated string".to_string())
+}
+
+/// Checks that nothing but whitespace or a comment follows a value.
+fn expect_end(rest: &str) -> Result<(), String> {
+    let rest = rest.trim_start();
+    if rest.is_empty() || rest.starts_with('#') {
+        Ok(())
+    } else {
+        Err(format!("unexpected {rest:?}"))
+    }
+}
+
+fn parse_integer<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    let value = value.split('#').next().unwrap_or_default().trim();
+    value
+        .parse()
+        .map_err(|_| format!("invalid integer {value:?}"))
+}
+
+impl Answers {
+    /// # Errors
+    ///
+    /// Returns an error, mentioning the offending line, if the text is not in
+    /// the format described in [`Answers`].
+    pub fn parse(text: &str) -> Result<Self, String> {
+        // Fields of the table currently being read, alongside the line it
+        // started on.
+        struct Table {
+            line: usize,
+            input: String,
+            year: u16,
+            day: Option<u8>,
+            part1: Option<String>,
+            part2: Option<String>,
+        }
+
+        fn finish(table: Table) -> Result<Entry, String> {
+            let day = table
+                .day
+                .ok_or_else(|| format!("line {}: table is missing a day", table.line))?;
+            Ok(Entry {
+                input: table.input,
+                key: Key::new(table.year, day),
+                part1: table.part1,
+                part2: table.part2,
+            })
+        }
+
+        let mut entries = Vec::new();
+        let mut table: Option<Table> = None;
+
+        for (i, line) in text.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.trim();
+            let error = |message: String| format!("line {line_number}: {message}");
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let (input, rest) = if header.starts_with('"') {
+                    parse_string(header).map_err(error)?
+                } else {
+                    let end = header.find(']').ok_or_else(|| error("expected ]".into()))?;
+                    (header[..end].trim().to_string(), &header[end..])
+                };
+                let rest = rest.trim_start().strip_prefix(']');
+                let rest = rest.ok_or_else(|| error("expected ]".into()))?;
+                expect_end(rest).map_err(error)?;
+
+                if let Some(previous) = table.take() {
+                    entries.push(finish(previous)?);
+                }
+                table = Some(Table {
+                    line: line_number,
+                    input,
+                    year: DEFAULT_YEAR,
+                    day: None,
+                    part1: None,
+                    part2: None,
+                });
+                continue;
+            }
+
+            let (name, value) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected a table header or key = value".into()))?;
+            let current = table
+                .as_mut()
+                .ok_or_else(|| error("key outside of a table".into()))?;
+            let value = value.trim();
+
+            match name.trim() {
+                "year" => current.year = parse_integer(value).map_err(error)?,
+                "day" => current.day = Some(parse_integer(value).map_err(error)?),
+                name @ ("part1" | "part2") => {
+                    let (answer, rest) = parse_string(value).map_err(error)?;
+                    expect_end(rest).map_err(error)?;
+                    if name == "part1" {
+                        current.part1 = Some(answer);
+                    } else {
+                        current.part2 = Some(answer);
+                    }
+                }
+                name => return Err(error(format!("unknown key {name:?}"))),
+            }
+        }
+
+        if let Some(last) = table {
+            entries.push(finish(last)?);
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|err| format!("could not read {}: {err}", path.display()))?;
+        Self::parse(&text).map_err(|err| format!("{}: {err}", path.display()))
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter()
+    }
+
+    #[must_use]
+    pub fn get(&self, key: Key, input: &str) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.key == key && normalize(&entry.input) == normalize(input))
+    }
+
+    /// Outputs the known answer to the given part of a day on an input.
+    #[must_use]
+    pub fn expected(&self, key: Key, part: u8, input: &str) -> Option<&str> {
+        self.get(key, input)?.expected(part)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The answer matches the known one.
+    Pass,
+    /// The answer differs from the known one, or could not be computed.
+    Fail,
+    /// There is no known answer to compare against.
+    Unknown,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail => write!(f, "fail"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Outcome of checking a single part of an [`Entry`].
+#[derive(Debug)]
+pub struct Verification {
+    pub expected: Option<String>,
+    pub report: RunReport,
+}
+
+impl Verification {
+    #[must_use]
+    pub fn status(&self) -> Status {
+        match (&self.expected, &self.report.result) {
+            (None, _) => Status::Unknown,
+            (Some(expected), Ok(answer)) if expected == answer => Status::Pass,
+            _ => Status::Fail,
+        }
+    }
+
+    /// Formats the verification as a single-line JSON object, with the same
+    /// fields as [`RunReport::to_json`] plus the expected answer and verdict.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let report = self.report.to_json();
+        let expected = self
+            .expected
+            .as_deref()
+            .map_or_else(|| "null".to_string(), json_string);
+        format!(
+            "{},\"expected\":{expected},\"verdict\":\"{}\"}}",
+            &report[..report.len() - 1],
+            self.status()
+        )
+    }
+}
+
+/// Runs both parts of every entry, each limited by `options`, comparing the
+/// results to the known answers.
+#[must_use]
+pub fn verify(answers: &Answers, options: &RunOptions) -> Vec<Verification> {
+    let mut output = Vec::new();
+    for entry in answers.entries() {
+        for report in run_parts(entry.key, &[1, 2], &entry.input, options) {
+            output.push(Verification {
+                expected: entry.expected(report.part).map(String::from),
+                report,
+            });
+        }
+    }
+    output
+}
+
+/// Runs the given part of the solution registered under `key` on each of its
+/// worked examples that has an answer for that part, each limited by
+/// `options`, comparing the results to those answers.
+///
+/// The input of each report is named `example <n>`, numbering the examples
+/// from 1. Parameters an example sets take precedence over those in
+/// `options`.
+///
+/// # Errors
+///
+/// Returns an error if the part cannot be run.
+pub fn verify_examples(
+    key: Key,
+    part: u8,
+    options: &RunOptions,
+) -> Result<Vec<Verification>, RunError> {
+    let solution = find_solution(key, part)?;
+    let mut output = Vec::new();
+
+    for (i, example) in solution.examples().iter().enumerate() {
+        let Some(expected) = example.expected(part) else {
+            continue;
+        };
+
+        let input = example.input;
+        let mut options = options.clone();
+        options.params.extend(
+            example
+                .params
+                .iter()
+                .map(|&(name, value)| (name.to_string(), value.to_string())),
+        );
+        let attempt = run_isolated(
+            solution,
+            part,
+            None,
+            move || Ok(lines_from_str(input)),
+            options,
+        );
+        output.push(Verification {
+            expected: Some(expected.to_string()),
+            report: RunReport {
+                key,
+                part,
+                path: format!("example {}", i + 1),
+                result: attempt.result,
+                parse_elapsed: attempt.parse_elapsed,
+                elapsed: attempt.elapsed,
+            },
+        });
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(
+            r#"
+            # A comment
+            ["inputs/2024/day01.txt"]
+            day = 1
+            part1 = "2176849" # trailing comment
+            part2 = "23384288"
+
+            [example]
+            year = 2023
+            day = 25 # another
+            part1 = "a \"quoted\" # answer"
+            "#,
+        )
+        .unwrap();
+
+        let entries: Vec<_> = answers.entries().collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            answers.expected(Key::new(2024, 1), 2, "./inputs/2024/day01.txt"),
+            Some("23384288")
+        );
+        assert_eq!(entries[1].input, "example");
+        assert_eq!(entries[1].key, Key::new(2023, 25));
+        assert_eq!(entries[1].expected(1), Some("a \"quoted\" # answer"));
+        assert_eq!(entries[1].expected(2), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Answers::parse("day = 1").is_err());
+        assert!(Answers::parse("[a]\npart1 = \"1\"").is_err());
+        assert!(Answers::parse("[a]\nday = x").is_err());
+        assert!(Answers::parse("[a]\nday = 1\npart3 = \"1\"").is_err());
+        assert!(Answers::parse("[a]\nday = 1\npart1 = \"1").is_err());
+        assert!(Answers::parse("[\"a\"\nday = 1").is_err());
+
+        let err = Answers::parse("[a]\nday = 1\n\nnonsense").unwrap_err();
+        assert!(err.starts_with("line 4:"));
+    }
+}
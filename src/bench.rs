@@ -0,0 +1,278 @@
+use crate::common::{
+    find_solution, format_duration, json_string, open_input, solve, ParamValues, RunError,
+    STDIN_PATH,
+};
+use crate::solution::{Context, Key};
+use crate::utils::lines_from_str;
+use std::fmt::{self, Display};
+use std::io;
+use std::time::{Duration, Instant};
+
+/// Summary statistics of a set of timing samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Outputs the statistics of the given samples, or `None` if there are
+    /// none.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let min = *sorted.first()?;
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+
+        let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / n as f64;
+
+        // Sample standard deviation, which is zero for a single sample.
+        let variance = if n > 1 {
+            secs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            min,
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+
+    /// Formats the statistics as a JSON object, in milliseconds.
+    fn to_json(self) -> String {
+        format!(
+            "{{\"min_ms\":{},\"median_ms\":{},\"mean_ms\":{},\"stddev_ms\":{}}}",
+            self.min.as_secs_f64() * 1e3,
+            self.median.as_secs_f64() * 1e3,
+            self.mean.as_secs_f64() * 1e3,
+            self.stddev.as_secs_f64() * 1e3,
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// Number of timed runs.
+    pub iters: usize,
+    /// Number of untimed runs made before the timed ones.
+    pub warmup: usize,
+    /// Values of the solver's parameters, as in [`RunOptions`].
+    ///
+    /// [`RunOptions`]: crate::common::RunOptions
+    pub params: ParamValues,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            iters: 10,
+            warmup: 1,
+            params: ParamValues::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub key: Key,
+    pub part: u8,
+    pub options: BenchOptions,
+    /// Time spent opening and parsing the input.
+    pub parse: Stats,
+    /// Time spent solving the part from the parsed input.
+    pub solve: Stats,
+    /// Every distinct answer produced, including during warmup, together with
+    /// the number of runs that produced it, in the order first seen.
+    pub answers: Vec<(String, usize)>,
+}
+
+impl BenchReport {
+    #[must_use]
+    pub const fn is_deterministic(&self) -> bool {
+        self.answers.len() == 1
+    }
+
+    /// Formats the report as a single-line JSON object.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let answers: Vec<String> = self
+            .answers
+            .iter()
+            .map(|(answer, count)| {
+                format!("{{\"answer\":{},\"runs\":{count}}}", json_string(answer))
+            })
+            .collect();
+
+        format!(
+            "{{\"year\":{},\"day\":{},\"part\":{},\"iters\":{},\"warmup\":{},\
+             \"parse\":{},\"solve\":{},\"deterministic\":{},\"answers\":[{}]}}",
+            self.key.year,
+            self.key.day,
+            self.part,
+            self.options.iters,
+            self.options.warmup,
+            self.parse.to_json(),
+            self.solve.to_json(),
+            self.is_deterministic(),
+            answers.join(","),
+        )
+    }
+}
+
+impl Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Benchmarked part {} of {} over {} iterations ({} warmup)",
+            self.part, self.key, self.options.iters, self.options.warmup
+        )?;
+        writeln!(
+            f,
+            "{:<7}{:>12}{:>12}{:>12}{:>12}",
+            "", "min", "median", "mean", "stddev"
+        )?;
+        for (phase, stats) in [("parse", &self.parse), ("solve", &self.solve)] {
+            writeln!(
+                f,
+                "{phase:<7}{:>12}{:>12}{:>12}{:>12}",
+                format_duration(stats.min),
+                format_duration(stats.median),
+                format_duration(stats.mean),
+                format_duration(stats.stddev),
+            )?;
+        }
+
+        if self.is_deterministic() {
+            write!(f, "Answer: {}", self.answers[0].0)
+        } else {
+            write!(
+                f,
+                "Nondeterministic: {} distinct answers",
+                self.answers.len()
+            )?;
+            for (answer, count) in &self.answers {
+                write!(f, "\n  {answer} ({count} runs)")?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Repeatedly runs the given part of the solution registered under `key` on
+/// the input file located at `path`, timing the parse and solve phases of
+/// every run separately.
+///
+/// If `path` is `-`, the input is read from stdin once, before any run.
+///
+/// # Errors
+///
+/// Returns an error if there are no timed runs, the part cannot be run, the
+/// input file cannot be opened, or the solver fails on any run.
+pub fn bench(
+    key: Key,
+    part: u8,
+    path: &str,
+    options: BenchOptions,
+) -> Result<BenchReport, RunError> {
+    let no_runs = || RunError::BadArgument("--iters 0: at least one run must be timed".to_string());
+    if options.iters == 0 {
+        return Err(no_runs());
+    }
+    let solution = find_solution(key, part)?;
+    let iters = options.iters;
+
+    // Stdin can only be read once, so it is kept in memory for every run.
+    let stdin = if path == STDIN_PATH {
+        let input = io::read_to_string(io::stdin()).map_err(|source| RunError::MissingInput {
+            path: path.to_string(),
+            source,
+        })?;
+        Some(input)
+    } else {
+        None
+    };
+
+    let mut parse_samples = Vec::with_capacity(iters);
+    let mut solve_samples = Vec::with_capacity(iters);
+    let mut answers: Vec<(String, usize)> = Vec::new();
+    // Progress is not reported, as it would only disturb the timings.
+    let context = Context::new(solution.params()).with_values(&options.params);
+
+    for i in 0..options.warmup + iters {
+        let now = Instant::now();
+        let mut lines = match &stdin {
+            Some(input) => lines_from_str(input),
+            None => open_input(path)?,
+        };
+        let input = solution.parse(&mut lines);
+        let parse_time = now.elapsed();
+
+        let now = Instant::now();
+        let answer = solve(solution, part, &*input, &context)?;
+        let solve_time = now.elapsed();
+
+        if i >= options.warmup {
+            parse_samples.push(parse_time);
+            solve_samples.push(solve_time);
+        }
+
+        match answers.iter_mut().find(|(seen, _)| *seen == answer) {
+            Some((_, count)) => *count += 1,
+            None => answers.push((answer, 1)),
+        }
+    }
+
+    let parse = Stats::from_samples(&parse_samples).ok_or_else(no_runs)?;
+    let solve = Stats::from_samples(&solve_samples).ok_or_else(no_runs)?;
+
+    Ok(BenchReport {
+        key,
+        part,
+        options,
+        parse,
+        solve,
+        answers,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<Duration> = [4, 1, 3, 2].into_iter().map(Duration::from_secs).collect();
+        let stats = Stats::from_samples(&samples).unwrap();
+
+        assert_eq!(stats.min, Duration::from_secs(1));
+        assert_eq!(stats.median, Duration::from_millis(2500));
+        assert_eq!(stats.mean, Duration::from_millis(2500));
+
+        // The sample variance of 1, 2, 3, 4 is 5/3.
+        let expected = (5.0_f64 / 3.0).sqrt();
+        assert!((stats.stddev.as_secs_f64() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stats_single_and_empty() {
+        let stats = Stats::from_samples(&[Duration::from_millis(7)]).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(7));
+        assert_eq!(stats.stddev, Duration::ZERO);
+
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+}
@@ -0,0 +1,915 @@
+use crate::answers::{
+    verify, verify_examples, Answers, Status, Verification, DEFAULT_ANSWERS_PATH,
+};
+use crate::bench::{bench, BenchOptions};
+use crate::common::{
+    default_jobs, describe_parts, format_duration, input_path, json_string, parse_day,
+    parse_day_and_part, parse_run_args, run_all, run_parts, run_timed, take_year, ParamValues,
+    RunError, RunOptions, RunReport,
+};
+use crate::days;
+use crate::fetch::{Fetcher, UreqClient};
+use crate::log;
+use crate::progress::ProgressMode;
+use crate::repl::{repl, Session};
+use crate::scaffold::scaffold;
+use crate::solution::Key;
+use crate::submit::{Submitter, Verdict};
+use crate::watch::{watch, WatchOptions};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Parses a selection of days: either `all`, or an inclusive range such as
+/// `1-10`.
+#[must_use]
+pub fn parse_days(arg: &str) -> Option<RangeInclusive<u8>> {
+    if arg == "all" {
+        return Some(1..=25);
+    }
+
+    let (first, last) = arg.split_once('-')?;
+    let first: u8 = first.parse().ok()?;
+    let last: u8 = last.parse().ok()?;
+
+    if first == 0 || first > last {
+        return None;
+    }
+
+    Some(first..=last)
+}
+
+/// Splits arguments into positional ones and the values of `--option value`
+/// pairs, accepting only the given option names.
+///
+/// # Errors
+///
+/// Returns an error if an unknown option is given, or an option is missing
+/// its value.
+pub fn split_options<'a>(
+    args: &'a [String],
+    options: &[&str],
+) -> Result<(Vec<&'a str>, HashMap<&'a str, &'a str>), RunError> {
+    let mut positional = Vec::new();
+    let mut values = HashMap::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if let Some(name) = arg.strip_prefix("--") {
+            if !options.contains(&name) {
+                return Err(RunError::BadArgument(format!("unknown option {arg}")));
+            }
+            let value = args
+                .next()
+                .ok_or_else(|| RunError::BadArgument(format!("{arg} requires a value")))?;
+            values.insert(name, value.as_str());
+        } else {
+            positional.push(arg.as_str());
+        }
+    }
+
+    Ok((positional, values))
+}
+
+fn parse_option<T: std::str::FromStr>(
+    values: &HashMap<&str, &str>,
+    name: &str,
+    default: T,
+) -> Result<T, RunError> {
+    values.get(name).map_or(Ok(default), |value| {
+        value
+            .parse()
+            .map_err(|_| RunError::BadArgument(format!("--{name} {value}")))
+    })
+}
+
+/// Handles `bench <day> <part> [path] [--iters N] [--warmup N]`, where `args`
+/// excludes the program name and subcommand.
+fn run_bench(
+    program: &str,
+    args: &[String],
+    year: u16,
+    format: Format,
+    params: ParamValues,
+) -> Result<u8, RunError> {
+    let (positional, values) = split_options(args, &["iters", "warmup"])?;
+    if positional.len() < 2 || positional.len() > 3 {
+        return Err(RunError::Usage(program.to_string()));
+    }
+
+    let (key, part) = parse_day_and_part(year, positional[0], positional[1])?;
+    check_params(key, &params)?;
+    let path = positional
+        .get(2)
+        .map_or_else(|| input_path(key), ToString::to_string);
+
+    let defaults = BenchOptions::default();
+    let options = BenchOptions {
+        iters: parse_option(&values, "iters", defaults.iters)?,
+        warmup: parse_option(&values, "warmup", defaults.warmup)?,
+        params,
+    };
+
+    let report = bench(key, part, &path, options)?;
+    match format {
+        Format::Text => println!("{report}"),
+        Format::Json => println!("{}", report.to_json()),
+    }
+
+    Ok(u8::from(!report.is_deterministic()))
+}
+
+/// How results are printed to stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Human readable text.
+    Text,
+    /// One JSON object per line.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = RunError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(RunError::BadArgument(format!("--format {s}"))),
+        }
+    }
+}
+
+/// Removes a `--format <text|json>` option from anywhere in the arguments,
+/// outputting the remaining arguments and the format, which defaults to text.
+fn take_format(args: &[String]) -> Result<(Vec<String>, Format), RunError> {
+    let Some(i) = args.iter().position(|arg| arg == "--format") else {
+        return Ok((args.to_vec(), Format::Text));
+    };
+
+    let format = args
+        .get(i + 1)
+        .ok_or_else(|| RunError::BadArgument("--format requires a value".to_string()))?
+        .parse()?;
+
+    let mut rest = args.to_vec();
+    rest.drain(i..i + 2);
+
+    Ok((rest, format))
+}
+
+/// How long, in seconds, each part may run for unless `--timeout` says
+/// otherwise.
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+/// Removes a `--timeout <seconds>` option from anywhere in the arguments,
+/// outputting the remaining arguments and the limits it sets on running
+/// solvers. The timeout defaults to [`DEFAULT_TIMEOUT_SECS`], and 0 disables
+/// it.
+fn take_timeout(args: &[String]) -> Result<(Vec<String>, RunOptions), RunError> {
+    let Some(i) = args.iter().position(|arg| arg == "--timeout") else {
+        let timeout = Some(Duration::from_secs(DEFAULT_TIMEOUT_SECS));
+        let options = RunOptions {
+            timeout,
+            ..RunOptions::default()
+        };
+        return Ok((args.to_vec(), options));
+    };
+
+    let value = args
+        .get(i + 1)
+        .ok_or_else(|| RunError::BadArgument("--timeout requires a value".to_string()))?;
+    let secs: u64 = value
+        .parse()
+        .map_err(|_| RunError::BadArgument(format!("--timeout {value}")))?;
+
+    let mut rest = args.to_vec();
+    rest.drain(i..i + 2);
+
+    let timeout = (secs > 0).then(|| Duration::from_secs(secs));
+    let options = RunOptions {
+        timeout,
+        ..RunOptions::default()
+    };
+    Ok((rest, options))
+}
+
+/// Removes every `--param <name>=<value>` option from the arguments,
+/// outputting the remaining arguments and the parameter values, in order.
+fn take_params(args: &[String]) -> Result<(Vec<String>, ParamValues), RunError> {
+    let mut rest = Vec::new();
+    let mut params = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg != "--param" {
+            rest.push(arg.clone());
+            continue;
+        }
+
+        let value = args
+            .next()
+            .ok_or_else(|| RunError::BadArgument("--param requires a value".to_string()))?;
+        let (name, value) = value
+            .split_once('=')
+            .filter(|(name, _)| !name.is_empty())
+            .ok_or_else(|| RunError::BadArgument(format!("--param {value}")))?;
+        params.push((name.to_string(), value.to_string()));
+    }
+
+    Ok((rest, params))
+}
+
+/// Checks that the day registered under `key` has every parameter given a
+/// value, so that a misspelt name is not silently ignored.
+fn check_params(key: Key, params: &[(String, String)]) -> Result<(), RunError> {
+    let Some(solution) = days::registry().get(key) else {
+        return Ok(());
+    };
+
+    let declared = solution.params();
+    for (name, _) in params {
+        if !declared.iter().any(|param| param.name == name) {
+            let names: Vec<&str> = declared.iter().map(|param| param.name).collect();
+            let names = if names.is_empty() {
+                "none".to_string()
+            } else {
+                names.join(", ")
+            };
+            return Err(RunError::BadArgument(format!(
+                "{key} has no parameter {name} (its parameters: {names})"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Removes every `-v` and `-vv` flag from the arguments, outputting the
+/// remaining arguments and how verbose logging should be: one level per `v`.
+fn take_verbosity(args: &[String]) -> (Vec<String>, u8) {
+    let mut verbosity = 0;
+    let rest = args
+        .iter()
+        .filter(|arg| match arg.as_str() {
+            "-v" => {
+                verbosity += 1;
+                false
+            }
+            "-vv" => {
+                verbosity += 2;
+                false
+            }
+            _ => true,
+        })
+        .cloned()
+        .collect();
+    (rest, verbosity)
+}
+
+/// Removes every occurrence of a flag taking no value from the arguments,
+/// outputting the remaining arguments and whether the flag was present.
+fn take_flag(args: &[String], flag: &str) -> (Vec<String>, bool) {
+    let rest: Vec<String> = args.iter().filter(|arg| *arg != flag).cloned().collect();
+    let present = rest.len() < args.len();
+    (rest, present)
+}
+
+/// Handles `<day> <part> --example`, running the part on each of the day's
+/// worked examples instead of an input file.
+fn run_examples(
+    args: &[String],
+    year: u16,
+    format: Format,
+    options: &RunOptions,
+) -> Result<u8, RunError> {
+    if args.len() != 3 {
+        let program = args.first().map_or("aoc", String::as_str);
+        return Err(RunError::Usage(program.to_string()));
+    }
+
+    let (key, part) = parse_day_and_part(year, &args[1], &args[2])?;
+    check_params(key, &options.params)?;
+    let verifications = verify_examples(key, part, options)?;
+    if verifications.is_empty() {
+        return Err(RunError::BadArgument(format!(
+            "{key} has no example with an answer to part {part}"
+        )));
+    }
+
+    print_verifications(&verifications, format);
+
+    Ok(u8::from(
+        verifications.iter().any(|v| v.status() == Status::Fail),
+    ))
+}
+
+/// Handles `<day> <1 | 2 | both> [path]`, printing the answer to each part on
+/// its own line.
+fn run_single(
+    args: &[String],
+    year: u16,
+    format: Format,
+    options: &RunOptions,
+) -> Result<u8, RunError> {
+    let (key, parts, path) = parse_run_args(args, year)?;
+    check_params(key, &options.params)?;
+
+    eprintln!(
+        "Running {} of {key} using input {path}.",
+        describe_parts(&parts)
+    );
+    eprintln!();
+
+    let reports = run_parts(key, &parts, &path, options);
+    for report in &reports {
+        match (format, &report.result) {
+            (Format::Json, _) => println!("{}", report.to_json()),
+            (Format::Text, Ok(answer)) => println!("{answer}"),
+            (Format::Text, Err(err)) => eprintln!("{err}"),
+        }
+    }
+
+    Ok(reports
+        .iter()
+        .find_map(|report| report.result.as_ref().err())
+        .map_or(0, RunError::exit_code))
+}
+
+/// Handles `<all | first_day-last_day> [--jobs N]`.
+fn run_days(
+    args: &[String],
+    year: u16,
+    days: RangeInclusive<u8>,
+    format: Format,
+    mut options: RunOptions,
+) -> Result<u8, RunError> {
+    let (positional, values) = split_options(&args[2..], &["jobs"])?;
+    if !positional.is_empty() {
+        let program = args.first().map_or("aoc", String::as_str);
+        return Err(RunError::Usage(program.to_string()));
+    }
+
+    let jobs: usize = parse_option(&values, "jobs", default_jobs())?;
+    if jobs == 0 {
+        return Err(RunError::BadArgument(
+            "--jobs must be at least 1".to_string(),
+        ));
+    }
+
+    // Bars of days running at the same time would be drawn over each other.
+    if jobs > 1 && options.progress == ProgressMode::Bar {
+        options.progress = ProgressMode::Silent;
+    }
+
+    let now = Instant::now();
+    let reports = run_all(year, days, jobs, &options);
+    let wall = now.elapsed();
+    match format {
+        Format::Text => {
+            print!("{}", summary_table(&reports));
+            let plural = if jobs == 1 { "" } else { "s" };
+            println!(
+                "Wall time: {} on {jobs} worker{plural}",
+                format_duration(wall)
+            );
+        }
+        Format::Json => {
+            for report in &reports {
+                println!("{}", report.to_json());
+            }
+        }
+    }
+
+    Ok(u8::from(!reports.iter().all(RunReport::is_success)))
+}
+
+/// Handles `verify [answers_file]`, where `args` excludes the program name and
+/// subcommand.
+fn run_verify(
+    program: &str,
+    args: &[String],
+    format: Format,
+    options: &RunOptions,
+) -> Result<u8, RunError> {
+    if args.len() > 1 {
+        return Err(RunError::Usage(program.to_string()));
+    }
+
+    let path = args.first().map_or(DEFAULT_ANSWERS_PATH, String::as_str);
+    let answers = Answers::load(path).map_err(RunError::BadAnswers)?;
+    let verifications = verify(&answers, options);
+
+    print_verifications(&verifications, format);
+
+    Ok(u8::from(
+        verifications.iter().any(|v| v.status() == Status::Fail),
+    ))
+}
+
+/// Handles `new <year> <day> [name]`, where `args` excludes the program name
+/// and subcommand.
+fn run_new(program: &str, args: &[String], format: Format) -> Result<u8, RunError> {
+    if args.len() < 2 || args.len() > 3 {
+        return Err(RunError::Usage(program.to_string()));
+    }
+
+    let year = args[0]
+        .parse()
+        .map_err(|_| RunError::BadArgument(format!("year {}", args[0])))?;
+    let day = match args[1].parse() {
+        Ok(n) if n > 0 => n,
+        _ => return Err(RunError::BadDay(args[1].clone())),
+    };
+    let name = args
+        .get(2)
+        .map_or_else(|| format!("Day {day}"), ToString::to_string);
+
+    let root = std::env::current_dir().map_err(|err| RunError::Scaffold(err.to_string()))?;
+    let changed = scaffold(&root, Key::new(year, day), &name).map_err(RunError::Scaffold)?;
+    let changed: Vec<String> = changed
+        .iter()
+        .map(|path| {
+            path.strip_prefix(&root)
+                .unwrap_or(path)
+                .display()
+                .to_string()
+        })
+        .collect();
+
+    match format {
+        Format::Text => {
+            for path in &changed {
+                println!("Wrote {path}");
+            }
+        }
+        Format::Json => {
+            let files: Vec<String> = changed.iter().map(|path| json_string(path)).collect();
+            println!("{{\"files\":[{}]}}", files.join(","));
+        }
+    }
+
+    Ok(0)
+}
+
+/// Handles `fetch <day>`, where `args` excludes the program name and
+/// subcommand.
+fn run_fetch(program: &str, args: &[String], year: u16, format: Format) -> Result<u8, RunError> {
+    if args.len() != 1 {
+        return Err(RunError::Usage(program.to_string()));
+    }
+
+    let key = parse_day(year, &args[0])?;
+    if !(1..=25).contains(&key.day) {
+        return Err(RunError::BadDay(args[0].clone()));
+    }
+
+    let fetched = Fetcher::from_env(&UreqClient)
+        .and_then(|fetcher| fetcher.fetch(key))
+        .map_err(RunError::Fetch)?;
+    let path = fetched.path.display().to_string();
+
+    match format {
+        Format::Text if fetched.downloaded => println!("Downloaded {path}"),
+        Format::Text => println!("Already cached at {path}"),
+        Format::Json => println!(
+            "{{\"year\":{},\"day\":{},\"path\":{},\"downloaded\":{}}}",
+            key.year,
+            key.day,
+            json_string(&path),
+            fetched.downloaded
+        ),
+    }
+
+    Ok(0)
+}
+
+/// Handles `submit <day> <part> [path]`, where `args` excludes the program name
+/// and subcommand, running the part and submitting its answer.
+fn run_submit(
+    program: &str,
+    args: &[String],
+    year: u16,
+    format: Format,
+    options: &RunOptions,
+) -> Result<u8, RunError> {
+    if args.is_empty() || args.len() > 3 {
+        return Err(RunError::Usage(program.to_string()));
+    }
+
+    let (key, part) = parse_day_and_part(year, &args[0], args.get(1).map_or("", String::as_str))?;
+    check_params(key, &options.params)?;
+    let path = args.get(2).map_or_else(|| input_path(key), Clone::clone);
+
+    eprintln!("Running part {part} of {key} using input {path}.");
+    let answer = run_timed(key, part, &path, options).result?;
+    eprintln!("Submitting {answer}.");
+    eprintln!();
+
+    let submission = Submitter::from_env(&UreqClient)
+        .and_then(|submitter| submitter.submit(key, part, &answer))
+        .map_err(RunError::Submit)?;
+
+    match format {
+        Format::Text => {
+            let wait = submission
+                .wait
+                .map_or_else(String::new, |wait| format!(" (wait {wait}s)"));
+            println!("{}: {}{wait}", submission.answer, submission.verdict);
+        }
+        Format::Json => println!(
+            "{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{},\"verdict\":\"{}\",\"wait_s\":{}}}",
+            key.year,
+            key.day,
+            part,
+            json_string(&submission.answer),
+            submission.verdict,
+            submission
+                .wait
+                .map_or_else(|| "null".to_string(), |wait| wait.to_string()),
+        ),
+    }
+
+    Ok(u8::from(submission.verdict != Verdict::Correct))
+}
+
+/// Handles `watch <day> <part> [path] [--example] [--interval MS]`, where
+/// `args` excludes the program name and subcommand.
+fn run_watch(program: &str, args: &[String], year: u16, run: RunOptions) -> Result<u8, RunError> {
+    let (args, examples) = take_flag(args, "--example");
+    let (positional, values) = split_options(&args, &["interval"])?;
+    if positional.len() < 2 || positional.len() > 3 {
+        return Err(RunError::Usage(program.to_string()));
+    }
+
+    let (key, part) = parse_day_and_part(year, positional[0], positional[1])?;
+    check_params(key, &run.params)?;
+    let path = positional
+        .get(2)
+        .map_or_else(|| input_path(key), ToString::to_string);
+
+    let defaults = WatchOptions::default();
+    let interval = parse_option(&values, "interval", defaults.interval.as_millis())?;
+    let options = WatchOptions {
+        interval: Duration::from_millis(interval.try_into().unwrap_or(u64::MAX)),
+        examples,
+        run,
+    };
+
+    watch(key, part, &path, options)?;
+    Ok(0)
+}
+
+/// Handles `repl [day]`, where `args` excludes the program name and
+/// subcommand.
+fn run_repl(
+    program: &str,
+    args: &[String],
+    year: u16,
+    options: RunOptions,
+) -> Result<u8, RunError> {
+    if args.len() > 1 {
+        return Err(RunError::Usage(program.to_string()));
+    }
+
+    let mut session = Session::new(year, options);
+    if let Some(day) = args.first() {
+        println!("{}", session.execute(&format!("day {day}"))?);
+    }
+
+    eprintln!("Type help for a list of commands.");
+    repl(session).map_err(|err| RunError::BadArgument(err.to_string()))?;
+    Ok(0)
+}
+
+fn print_verifications(verifications: &[Verification], format: Format) {
+    match format {
+        Format::Text => print!("{}", verification_table(verifications)),
+        Format::Json => {
+            for verification in verifications {
+                println!("{}", verification.to_json());
+            }
+        }
+    }
+}
+
+fn dispatch(args: &[String], format: Format) -> Result<u8, RunError> {
+    let (args, year) = take_year(args)?;
+    let (args, mut options) = take_timeout(&args)?;
+    let (args, params) = take_params(&args)?;
+    options.progress = ProgressMode::detect(format == Format::Json);
+    options.params = params;
+
+    match args.get(1).map(String::as_str) {
+        Some("bench") => return run_bench(&args[0], &args[2..], year, format, options.params),
+        Some("verify") => return run_verify(&args[0], &args[2..], format, &options),
+        Some("new") => return run_new(&args[0], &args[2..], format),
+        Some("fetch") => return run_fetch(&args[0], &args[2..], year, format),
+        Some("submit") => return run_submit(&args[0], &args[2..], year, format, &options),
+        Some("watch") => return run_watch(&args[0], &args[2..], year, options),
+        Some("repl") => return run_repl(&args[0], &args[2..], year, options),
+        _ => {}
+    }
+
+    if let Some(days) = args.get(1).and_then(|arg| parse_days(arg)) {
+        return run_days(&args, year, days, format, options);
+    }
+
+    let (args, example) = take_flag(&args, "--example");
+    if example {
+        return run_examples(&args, year, format, &options);
+    }
+
+    run_single(&args, year, format, &options)
+}
+
+/// Runs the program with the given command line arguments, printing any
+/// output, and outputs the exit code the process should report.
+#[must_use]
+pub fn run_cli(args: &[String]) -> u8 {
+    let (args, verbosity) = take_verbosity(args);
+    if verbosity > 0 && !cfg!(debug_assertions) {
+        eprintln!("Logging is compiled out of release builds, so -v has no effect.");
+    }
+    log::set_verbosity(verbosity);
+
+    take_format(&args)
+        .and_then(|(args, format)| dispatch(&args, format))
+        .unwrap_or_else(|err| {
+            eprintln!("{err}");
+            err.exit_code()
+        })
+}
+
+/// Lays out rows in columns padded to their widest cell, right aligning the
+/// columns marked in `right` and leaving the last column unpadded.
+fn format_table<const N: usize>(
+    header: [&str; N],
+    rows: &[[String; N]],
+    right: [bool; N],
+) -> String {
+    let mut widths = header.map(str::len);
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut output = String::new();
+    let mut push_row = |cells: [&str; N]| {
+        let mut line = String::new();
+        for (i, cell) in cells.iter().enumerate() {
+            if i + 1 == N {
+                line.push_str(cell);
+            } else if right[i] {
+                let _ = write!(line, "{cell:>width$}  ", width = widths[i]);
+            } else {
+                let _ = write!(line, "{cell:<width$}  ", width = widths[i]);
+            }
+        }
+        output.push_str(&line);
+        output.push('\n');
+    };
+
+    push_row(header);
+    for row in rows {
+        push_row(row.each_ref().map(String::as_str));
+    }
+
+    output
+}
+
+/// Formats the reports of [`run_all`] as a table with one row per day and
+/// part, followed by the total time taken.
+#[must_use]
+pub fn summary_table(reports: &[RunReport]) -> String {
+    let rows: Vec<[String; 7]> = reports
+        .iter()
+        .map(|report| {
+            let name = days::registry()
+                .get(report.key)
+                .map_or("", |solution| solution.name());
+            let answer = report.result.as_ref().map_or("-", String::as_str);
+            let parse = report
+                .parse_elapsed
+                .map_or_else(|| "-".to_string(), format_duration);
+            let solve = if matches!(
+                report.result,
+                Err(RunError::Unimplemented { .. } | RunError::MissingInput { .. })
+            ) {
+                "-".to_string()
+            } else {
+                format_duration(report.elapsed)
+            };
+            [
+                report.key.day.to_string(),
+                report.part.to_string(),
+                name.to_string(),
+                answer.to_string(),
+                parse,
+                solve,
+                report.status(),
+            ]
+        })
+        .collect();
+
+    let mut output = format_table(
+        ["Day", "Part", "Name", "Answer", "Parse", "Solve", "Status"],
+        &rows,
+        [true, true, false, false, true, true, false],
+    );
+
+    let total: Duration = reports
+        .iter()
+        .map(|report| report.parse_elapsed.unwrap_or_default() + report.elapsed)
+        .sum();
+    let solved = reports
+        .iter()
+        .filter(|report| report.result.is_ok())
+        .count();
+    let unimplemented = reports
+        .iter()
+        .filter(|report| matches!(report.result, Err(RunError::Unimplemented { .. })))
+        .count();
+    let failed = reports.len() - solved - unimplemented;
+    let _ = writeln!(
+        output,
+        "\nTotal: {} ({solved} ok, {unimplemented} not implemented, {failed} failed)",
+        format_duration(total)
+    );
+
+    output
+}
+
+/// Formats the outcome of [`verify`] as a table with one row per input and
+/// part, followed by the number of entries of each status.
+#[must_use]
+pub fn verification_table(verifications: &[Verification]) -> String {
+    let rows: Vec<[String; 6]> = verifications
+        .iter()
+        .map(|verification| {
+            let report = &verification.report;
+            let answer = report
+                .result
+                .as_ref()
+                .map_or_else(|_| report.status(), Clone::clone);
+            [
+                report.key.day.to_string(),
+                report.part.to_string(),
+                report.path.clone(),
+                verification.expected.clone().unwrap_or_else(|| "-".into()),
+                answer,
+                verification.status().to_string(),
+            ]
+        })
+        .collect();
+
+    let mut output = format_table(
+        ["Day", "Part", "Input", "Expected", "Answer", "Status"],
+        &rows,
+        [true, true, false, false, false, false],
+    );
+
+    let count = |status| {
+        verifications
+            .iter()
+            .filter(|verification| verification.status() == status)
+            .count()
+    };
+    let _ = writeln!(
+        output,
+        "\n{} passed, {} failed, {} unknown",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Unknown)
+    );
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("all"), Some(1..=25));
+        assert_eq!(parse_days("1-10"), Some(1..=10));
+        assert_eq!(parse_days("7-7"), Some(7..=7));
+        assert_eq!(parse_days("10-1"), None);
+        assert_eq!(parse_days("0-3"), None);
+        assert_eq!(parse_days("5"), None);
+        assert_eq!(parse_days("a-b"), None);
+    }
+
+    #[test]
+    fn test_split_options() {
+        let args: Vec<String> = ["6", "--iters", "5", "2"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let (positional, values) = split_options(&args, &["iters"]).unwrap();
+        assert_eq!(positional, vec!["6", "2"]);
+        assert_eq!(values.get("iters"), Some(&"5"));
+
+        assert!(split_options(&args, &["warmup"]).is_err());
+        assert!(split_options(&args[..2], &["iters"]).is_err());
+    }
+
+    #[test]
+    fn test_take_format() {
+        let args: Vec<String> = ["aoc", "--format", "json", "1", "2"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let (rest, format) = take_format(&args).unwrap();
+        assert_eq!(rest, vec!["aoc", "1", "2"]);
+        assert_eq!(format, Format::Json);
+
+        let (rest, format) = take_format(&rest).unwrap();
+        assert_eq!(rest, vec!["aoc", "1", "2"]);
+        assert_eq!(format, Format::Text);
+
+        assert!(take_format(&args[..2]).is_err());
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_take_timeout() {
+        let args: Vec<String> = ["aoc", "all", "--timeout", "5"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let (rest, options) = take_timeout(&args).unwrap();
+        assert_eq!(rest, vec!["aoc", "all"]);
+        assert_eq!(options.timeout, Some(Duration::from_secs(5)));
+
+        let (_, options) = take_timeout(&rest).unwrap();
+        assert_eq!(
+            options.timeout,
+            Some(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+        );
+
+        let args: Vec<String> = ["aoc", "--timeout", "0"].map(String::from).to_vec();
+        assert_eq!(take_timeout(&args).unwrap().1.timeout, None);
+        assert!(take_timeout(&args[..2]).is_err());
+    }
+
+    #[test]
+    fn test_take_verbosity() {
+        let args: Vec<String> = ["aoc", "-v", "17", "-vv", "1"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(
+            take_verbosity(&args),
+            (
+                vec!["aoc".to_string(), "17".to_string(), "1".to_string()],
+                3
+            )
+        );
+        assert_eq!(take_verbosity(&args[..1]).1, 0);
+    }
+
+    #[test]
+    fn test_take_params() {
+        let args: Vec<String> = ["aoc", "--param", "size=7", "18", "--param", "bytes=12", "1"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let (rest, params) = take_params(&args).unwrap();
+        assert_eq!(rest, ["aoc", "18", "1"]);
+        assert_eq!(
+            params,
+            [
+                ("size".to_string(), "7".to_string()),
+                ("bytes".to_string(), "12".to_string())
+            ]
+        );
+
+        assert!(take_params(&args[..2]).is_err());
+        assert!(take_params(&["--param".to_string(), "size".to_string()]).is_err());
+        assert!(take_params(&["--param".to_string(), "=7".to_string()]).is_err());
+
+        assert!(check_params(Key::new(2024, 18), &params).is_ok());
+        let params = [("width".to_string(), "11".to_string())];
+        assert!(check_params(Key::new(2024, 18), &params).is_err());
+    }
+
+    #[test]
+    fn test_take_flag() {
+        let args: Vec<String> = ["aoc", "1", "--example", "2"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let (rest, present) = take_flag(&args, "--example");
+        assert_eq!(rest, vec!["aoc", "1", "2"]);
+        assert!(present);
+
+        let (rest, present) = take_flag(&rest, "--example");
+        assert_eq!(rest, vec!["aoc", "1", "2"]);
+        assert!(!present);
+    }
+}
@@ -1,121 +1,952 @@
 use super::days;
+use crate::fetch;
+use crate::progress::{Progress, ProgressMode};
+use crate::solution::{Context, Key, ParsedInput, Solution, SolveError};
+use crate::utils::{lines_from_reader, lines_from_str};
+use std::any::Any;
+use std::fmt::{self, Display, Write};
 use std::fs::File;
-use std::io::{self, BufRead};
+use std::io;
+use std::num::NonZeroUsize;
+use std::ops::RangeInclusive;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::Path;
-use std::process;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 
-pub type LinesIterator = std::io::Lines<std::io::BufReader<std::fs::File>>;
+pub use crate::utils::LinesIterator;
 
-#[allow(clippy::must_use_candidate)]
-pub fn run_w_args(args: &[String]) -> String {
-    if args.len() < 3 || args.len() > 4 {
-        eprintln!(
-            "Usage: {} <day_number> <part_1_or_2> [path (optional)]",
-            args[0]
-        );
-        process::exit(1);
+/// The path which, given in place of an input file, reads the input from stdin.
+pub const STDIN_PATH: &str = "-";
+
+/// The part argument which runs both parts of a day on a single parse of the
+/// input.
+pub const BOTH_PARTS: &str = "both";
+
+/// The year whose puzzles are run when none is specified.
+pub const DEFAULT_YEAR: u16 = 2024;
+
+/// Everything that can go wrong when running a solution.
+#[derive(Debug)]
+pub enum RunError {
+    /// The wrong number of arguments was given; holds the program name.
+    Usage(String),
+    /// The year is not a number, or has no registered solutions.
+    BadYear(String),
+    /// The day is not a positive integer, or has no registered solution.
+    BadDay(String),
+    /// The part is neither 1 nor 2.
+    BadPart(String),
+    /// Some other argument or option is malformed.
+    BadArgument(String),
+    /// The answers file could not be read or parsed.
+    BadAnswers(String),
+    /// A new day could not be created.
+    Scaffold(String),
+    /// An input could not be downloaded.
+    Fetch(String),
+    /// An answer was not submitted, or its submission failed.
+    Submit(String),
+    /// The input file could not be opened.
+    MissingInput { path: String, source: io::Error },
+    /// The requested part has not been solved yet.
+    Unimplemented { key: Key, part: u8 },
+    /// The solver ran, but did not produce an answer.
+    SolverFailed { key: Key, part: u8, message: String },
+    /// The solver panicked while parsing the input or solving the part.
+    Panicked { key: Key, part: u8, message: String },
+    /// The solver was abandoned for taking longer than the timeout.
+    TimedOut {
+        key: Key,
+        part: u8,
+        timeout: Duration,
+    },
+}
+
+impl RunError {
+    /// The exit code a binary should report when it fails with this error.
+    #[must_use]
+    pub const fn exit_code(&self) -> u8 {
+        match self {
+            Self::SolverFailed { .. }
+            | Self::Panicked { .. }
+            | Self::TimedOut { .. }
+            | Self::Scaffold(_)
+            | Self::Fetch(_)
+            | Self::Submit(_) => 1,
+            Self::Usage(_)
+            | Self::BadYear(_)
+            | Self::BadDay(_)
+            | Self::BadPart(_)
+            | Self::BadArgument(_) => 2,
+            Self::MissingInput { .. } | Self::BadAnswers(_) => 3,
+            Self::Unimplemented { .. } => 4,
+        }
+    }
+
+    fn from_solve_error(err: SolveError, key: Key, part: u8) -> Self {
+        match err {
+            SolveError::Unimplemented => Self::Unimplemented { key, part },
+            SolveError::Failed(message) => Self::SolverFailed { key, part, message },
+        }
     }
+}
 
-    let day_number: u8 = match args[1].parse() {
-        Ok(n) if n > 0 => n,
-        _ => {
-            eprintln!("Please provide a valid positive integer for the day number");
-            process::exit(1);
+impl Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Usage(program) => write!(
+                f,
+                "Usage: {program} <day_number> <1 | 2 | both> [path | - | --example]\n       \
+                 {program} <all | first_day-last_day> [--jobs N]\n       \
+                 {program} bench <day_number> <part_1_or_2> [path] [--iters N] [--warmup N]\n       \
+                 {program} verify [answers_file]\n       \
+                 {program} new <year> <day_number> [name]\n       \
+                 {program} fetch <day_number>\n       \
+                 {program} submit <day_number> <part_1_or_2> [path]\n       \
+                 {program} watch <day_number> <part_1_or_2> [path] [--example] [--interval MS]\n       \
+                 {program} repl [day_number]\n\n\
+                 Options: --year <year> (default {DEFAULT_YEAR}), --format <text | json>, \
+                 --timeout <seconds> (0 for none), --param <name>=<value>, \
+                 -v | -vv (debug builds only)"
+            ),
+            Self::BadYear(year) => write!(
+                f,
+                "Invalid year {year:?}: please provide a year with registered solutions"
+            ),
+            Self::BadDay(day) => write!(
+                f,
+                "Invalid day {day:?}: please provide a valid positive integer for the day number"
+            ),
+            Self::BadPart(part) => write!(
+                f,
+                "Invalid part {part:?}: please provide 1, 2, or both to indicate which part"
+            ),
+            Self::BadArgument(message) => write!(f, "Invalid argument: {message}"),
+            Self::BadAnswers(message) => write!(f, "Invalid answers file: {message}"),
+            Self::Scaffold(message) => write!(f, "Could not create the day: {message}"),
+            Self::Fetch(message) => write!(f, "Could not fetch the input: {message}"),
+            Self::Submit(message) => write!(f, "Did not submit the answer: {message}"),
+            Self::MissingInput { path, source } => {
+                write!(f, "Could not open input file {path}: {source}")
+            }
+            Self::Unimplemented { key, part } => {
+                write!(f, "Part {part} of {key} is not implemented")
+            }
+            Self::SolverFailed { key, part, message } => {
+                write!(f, "Part {part} of {key} failed: {message}")
+            }
+            Self::Panicked { key, part, message } => {
+                write!(f, "Part {part} of {key} panicked: {message}")
+            }
+            Self::TimedOut { key, part, timeout } => write!(
+                f,
+                "Part {part} of {key} timed out after {}",
+                format_duration(*timeout)
+            ),
         }
+    }
+}
+
+impl std::error::Error for RunError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MissingInput { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Outputs the solution registered under `key`, provided that the given part
+/// of it can be run.
+///
+/// # Errors
+///
+/// Returns an error if there is no solution for `key`, `part` is neither 1
+/// nor 2, or the part has not been solved yet.
+pub fn find_solution(key: Key, part: u8) -> Result<&'static dyn Solution, RunError> {
+    if !days::registry().keys().any(|other| other.year == key.year) {
+        return Err(RunError::BadYear(key.year.to_string()));
+    }
+
+    let solution = days::registry()
+        .get(key)
+        .ok_or_else(|| RunError::BadDay(key.day.to_string()))?;
+
+    if !matches!(part, 1 | 2) {
+        return Err(RunError::BadPart(part.to_string()));
+    }
+
+    if !solution.is_implemented(part) {
+        return Err(RunError::Unimplemented { key, part });
+    }
+
+    Ok(solution)
+}
+
+/// Opens the input file located at `path`, or stdin if `path` is `-`.
+///
+/// # Errors
+///
+/// Returns an error if the input file cannot be opened.
+pub fn open_input(path: &str) -> Result<LinesIterator, RunError> {
+    open_lines(path).map_err(|source| RunError::MissingInput {
+        path: path.to_string(),
+        source,
+    })
+}
+
+fn open_lines(path: &str) -> io::Result<LinesIterator> {
+    if path == STDIN_PATH {
+        Ok(lines_from_reader(io::BufReader::new(io::stdin())))
+    } else {
+        read_lines(path)
+    }
+}
+
+/// Opens the input file located at `path`, or stdin if `path` is `-`, and
+/// parses it into the form both parts of a solution are solved from.
+///
+/// # Errors
+///
+/// Returns an error if the input file cannot be opened.
+pub fn parse_input(solution: &dyn Solution, path: &str) -> Result<ParsedInput, RunError> {
+    let mut lines = open_input(path)?;
+    Ok(solution.parse(&mut lines))
+}
+
+/// Runs the given part of a solution on an input it has parsed.
+///
+/// # Errors
+///
+/// Returns an error if `part` is neither 1 nor 2, or the solver does not
+/// produce an answer.
+pub fn solve(
+    solution: &dyn Solution,
+    part: u8,
+    input: &dyn Any,
+    context: &Context,
+) -> Result<String, RunError> {
+    solution
+        .solve(part, input, context)
+        .ok_or_else(|| RunError::BadPart(part.to_string()))?
+        .map_err(|err| RunError::from_solve_error(err, solution.key(), part))
+}
+
+/// Runs the given part of the solution registered under `key` on the input
+/// file located at `path`.
+///
+/// # Errors
+///
+/// Returns an error if there is no solution for `key`, `part` is neither 1
+/// nor 2, the input file cannot be opened, or the solver does not produce an
+/// answer.
+pub fn run(key: Key, part: u8, path: &str) -> Result<String, RunError> {
+    let solution = find_solution(key, part)?;
+    let input = parse_input(solution, path)?;
+    solve(solution, part, &*input, &Context::new(solution.params()))
+}
+
+/// Values of the solvers' parameters (see [`Param`]), by name.
+///
+/// [`Param`]: crate::solution::Param
+pub type ParamValues = Vec<(String, String)>;
+
+/// Limits on running solvers, applied to each part separately.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunOptions {
+    /// How long a part may take, including parsing the input if it has to,
+    /// before it is abandoned, or `None` to wait for as long as it takes.
+    pub timeout: Option<Duration>,
+    /// How solvers report their progress.
+    pub progress: ProgressMode,
+    /// Values of the solvers' parameters, later ones taking precedence.
+    /// Parameters the day being run does not have are ignored.
+    pub params: ParamValues,
+}
+
+/// The outcome of [`run_isolated`].
+pub struct Attempt {
+    /// The parsed input, for later parts to reuse, or `None` if it could not
+    /// be parsed or was lost with a part that timed out.
+    pub input: Option<ParsedInput>,
+    /// Time spent parsing the input, or `None` if it was given.
+    pub parse_elapsed: Option<Duration>,
+    /// Time spent solving the part, excluding any parsing.
+    pub elapsed: Duration,
+    pub result: Result<String, RunError>,
+}
+
+/// Outputs the message a panic was raised with.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown cause".to_string())
+}
+
+fn attempt<F>(
+    solution: &dyn Solution,
+    part: u8,
+    input: Option<ParsedInput>,
+    open: F,
+    options: &RunOptions,
+    cancelled: Arc<AtomicBool>,
+) -> Attempt
+where
+    F: FnOnce() -> Result<LinesIterator, RunError>,
+{
+    let key = solution.key();
+    let panicked = |payload: Box<dyn Any + Send>| RunError::Panicked {
+        key,
+        part,
+        message: panic_message(&*payload),
     };
 
-    let part: u8 = match args[2].parse() {
-        Ok(n) if (n == 1) || (n == 2) => n,
-        _ => {
-            eprintln!("Please provide either 1 or 2 to indicate which part");
-            process::exit(1);
+    let mut parse_elapsed = None;
+    let input = if let Some(input) = input {
+        input
+    } else {
+        let now = Instant::now();
+        let parsed = open().and_then(|mut lines| {
+            panic::catch_unwind(AssertUnwindSafe(|| solution.parse(&mut lines))).map_err(panicked)
+        });
+        parse_elapsed = Some(now.elapsed());
+
+        match parsed {
+            Ok(input) => input,
+            Err(err) => {
+                return Attempt {
+                    input: None,
+                    parse_elapsed,
+                    elapsed: Duration::ZERO,
+                    result: Err(err),
+                }
+            }
         }
     };
 
+    let context = Context {
+        progress: Progress::new(options.progress, key, part).cancelled_by(cancelled),
+        params: solution.params(),
+        values: options.params.iter().cloned().collect(),
+    };
+    let now = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        solve(solution, part, &*input, &context)
+    }))
+    .unwrap_or_else(|payload| Err(panicked(payload)));
+    let elapsed = now.elapsed();
+    context.progress.finish();
+
+    Attempt {
+        input: Some(input),
+        parse_elapsed,
+        elapsed,
+        result,
+    }
+}
+
+/// Runs the given part of a solution, reporting a panic in it as an error.
+///
+/// The input is first parsed from the lines output by `open`, unless `input`
+/// is given.
+///
+/// Without a timeout, the part runs on the calling thread. With one, it runs
+/// on a thread of its own, which is abandoned once the timeout passes: as it
+/// cannot be stopped, it keeps running in the background until it finishes or
+/// the process exits, though without reporting its progress any more.
+pub fn run_isolated<F>(
+    solution: &'static dyn Solution,
+    part: u8,
+    input: Option<ParsedInput>,
+    open: F,
+    options: RunOptions,
+) -> Attempt
+where
+    F: FnOnce() -> Result<LinesIterator, RunError> + Send + 'static,
+{
+    let Some(timeout) = options.timeout else {
+        return attempt(solution, part, input, open, &options, Arc::default());
+    };
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let (sender, receiver) = mpsc::channel();
+    let handle = Arc::clone(&cancelled);
+    thread::spawn(move || {
+        let _ = sender.send(attempt(solution, part, input, open, &options, handle));
+    });
+
+    let key = solution.key();
+    receiver.recv_timeout(timeout).unwrap_or_else(|err| {
+        cancelled.store(true, Ordering::Relaxed);
+        Attempt {
+            input: None,
+            parse_elapsed: None,
+            elapsed: timeout,
+            result: Err(match err {
+                RecvTimeoutError::Timeout => RunError::TimedOut { key, part, timeout },
+                RecvTimeoutError::Disconnected => RunError::Panicked {
+                    key,
+                    part,
+                    message: "the solver thread exited without an answer".to_string(),
+                },
+            }),
+        }
+    })
+}
+
+/// Runs the given parts of the solution registered under `key` on the input
+/// file located at `path`, in order, timing each of them.
+///
+/// Each part is isolated from the others as described in [`run_isolated`].
+///
+/// The input is parsed only once, when the first part that can be run needs
+/// it, and the time this takes is reported separately from the time taken to
+/// solve that part. It is only parsed again if that failed, or the input was
+/// lost with a part that timed out. Parts that cannot be run are reported
+/// without reading the input at all.
+#[must_use]
+pub fn run_parts(key: Key, parts: &[u8], path: &str, options: &RunOptions) -> Vec<RunReport> {
+    let mut input = None;
+    let mut reports = Vec::with_capacity(parts.len());
+
+    for &part in parts {
+        let attempt = match find_solution(key, part) {
+            Ok(solution) => {
+                let path = path.to_string();
+                run_isolated(
+                    solution,
+                    part,
+                    input.take(),
+                    move || open_input(&path),
+                    options.clone(),
+                )
+            }
+            Err(err) => Attempt {
+                input: input.take(),
+                parse_elapsed: None,
+                elapsed: Duration::ZERO,
+                result: Err(err),
+            },
+        };
+        input = attempt.input;
+
+        reports.push(RunReport {
+            key,
+            part,
+            path: path.to_string(),
+            result: attempt.result,
+            parse_elapsed: attempt.parse_elapsed,
+            elapsed: attempt.elapsed,
+        });
+    }
+
+    reports
+}
+
+/// Parses a day number given as a command line argument into the key of that
+/// day of `year`.
+///
+/// # Errors
+///
+/// Returns an error if the day is not a positive integer.
+pub fn parse_day(year: u16, day: &str) -> Result<Key, RunError> {
+    match day.parse() {
+        Ok(n) if n > 0 => Ok(Key::new(year, n)),
+        _ => Err(RunError::BadDay(day.to_string())),
+    }
+}
+
+/// Parses a part number given as a command line argument.
+///
+/// # Errors
+///
+/// Returns an error if the part is neither 1 nor 2.
+pub fn parse_part(part: &str) -> Result<u8, RunError> {
+    match part.parse() {
+        Ok(n) if (n == 1) || (n == 2) => Ok(n),
+        _ => Err(RunError::BadPart(part.to_string())),
+    }
+}
+
+/// Parses a part given as a command line argument: either 1, 2, or `both`.
+///
+/// # Errors
+///
+/// Returns an error if the part is none of these.
+pub fn parse_parts(part: &str) -> Result<Vec<u8>, RunError> {
+    if part == BOTH_PARTS {
+        Ok(vec![1, 2])
+    } else {
+        parse_part(part).map(|part| vec![part])
+    }
+}
+
+/// Parses a day number and part number given as command line arguments.
+///
+/// # Errors
+///
+/// Returns an error if the day is not a positive integer, or the part is
+/// neither 1 nor 2.
+pub fn parse_day_and_part(year: u16, day: &str, part: &str) -> Result<(Key, u8), RunError> {
+    Ok((parse_day(year, day)?, parse_part(part)?))
+}
+
+/// Removes a `--year <year>` option from anywhere in the arguments, outputting
+/// the remaining arguments and the year, which defaults to [`DEFAULT_YEAR`].
+///
+/// # Errors
+///
+/// Returns an error if the option is missing its value, or the value is not a
+/// number.
+pub fn take_year(args: &[String]) -> Result<(Vec<String>, u16), RunError> {
+    let Some(i) = args.iter().position(|arg| arg == "--year") else {
+        return Ok((args.to_vec(), DEFAULT_YEAR));
+    };
+
+    let year = args
+        .get(i + 1)
+        .ok_or_else(|| RunError::BadArgument("--year requires a value".to_string()))?;
+    let year = year.parse().map_err(|_| RunError::BadYear(year.clone()))?;
+
+    let mut rest = args.to_vec();
+    rest.drain(i..i + 2);
+
+    Ok((rest, year))
+}
+
+/// Runs the given part of a day of the default year on input held in memory.
+///
+/// # Errors
+///
+/// Returns an error if there is no solution for the day, `part` is neither 1
+/// nor 2, or the solver does not produce an answer.
+pub fn run_on_str(day: u8, part: u8, input: &str) -> Result<String, RunError> {
+    let key = Key::new(DEFAULT_YEAR, day);
+    let solution = find_solution(key, part)?;
+    let input = solution.parse(&mut lines_from_str(input));
+    solve(solution, part, &*input, &Context::new(solution.params()))
+}
+
+/// Parses command line arguments of the form
+/// `<program> <day_number> <1 | 2 | both> [path]` into the solution of the
+/// given year to run, the parts, and the path of the input.
+///
+/// # Errors
+///
+/// Returns an error if the arguments are malformed.
+pub fn parse_run_args(args: &[String], year: u16) -> Result<(Key, Vec<u8>, String), RunError> {
+    if args.len() < 3 || args.len() > 4 {
+        let program = args.first().map_or("aoc", String::as_str);
+        return Err(RunError::Usage(program.to_string()));
+    }
+
+    let key = parse_day(year, &args[1])?;
+    let parts = parse_parts(&args[2])?;
+
     let path: String = if args.len() == 4 {
         args[3].clone()
     } else {
-        format!("./inputs/input{day_number}.txt")
+        input_path(key)
     };
 
-    println!("Running part {part} of day {day_number} using input {path}.");
-    println!();
+    Ok((key, parts, path))
+}
 
-    let mut lines: LinesIterator = read_lines(path).unwrap_or_else(|err| {
-        eprintln!("{err}");
-        process::exit(1);
-    });
+/// Describes the parts being run, for messages such as "Running part 1".
+#[must_use]
+pub fn describe_parts(parts: &[u8]) -> String {
+    match parts {
+        [part] => format!("part {part}"),
+        _ => "both parts".to_string(),
+    }
+}
+
+/// Parses command line arguments of the form
+/// `<program> <day_number> <1 | 2 | both> [path] [--year <year>]` and runs the
+/// corresponding solution, outputting the answer to each part on its own
+/// line.
+///
+/// # Errors
+///
+/// Returns an error if the arguments are malformed, or if running any part of
+/// the solution fails (see [`run`]).
+pub fn run_w_args(args: &[String]) -> Result<String, RunError> {
+    let (args, year) = take_year(args)?;
+    let (key, parts, path) = parse_run_args(&args, year)?;
+
+    eprintln!(
+        "Running {} of {key} using input {path}.",
+        describe_parts(&parts)
+    );
+    eprintln!();
+
+    let answers = run_parts(key, &parts, &path, &RunOptions::default())
+        .into_iter()
+        .map(|report| report.result)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(answers.join("\n"))
+}
+
+/// Runs the given part of the solution registered under `key` on the input
+/// file located at `path`, timing how long it takes.
+#[must_use]
+pub fn run_timed(key: Key, part: u8, path: &str, options: &RunOptions) -> RunReport {
+    let mut reports = run_parts(key, &[part], path, options);
+    reports.remove(0)
+}
+
+/// Outputs the path of the input used for a day when none is specified.
+#[must_use]
+pub fn default_input_path(key: Key) -> String {
+    format!("inputs/{}/day{:02}.txt", key.year, key.day)
+}
+
+/// Outputs the path of the input to run a day on when none is specified.
+///
+/// This is the default path if there is a file there, otherwise the input
+/// downloaded by `fetch` if it is cached, and otherwise the default path
+/// regardless.
+#[must_use]
+pub fn input_path(key: Key) -> String {
+    input_path_in(key, fetch::cache_dir().as_deref())
+}
+
+/// Outputs the path of the input to run a day on when none is specified, as
+/// [`input_path`] does, looking for cached inputs in `cache_dir`, if any.
+#[must_use]
+pub fn input_path_in(key: Key, cache_dir: Option<&Path>) -> String {
+    let path = default_input_path(key);
+    if Path::new(&path).exists() {
+        return path;
+    }
+
+    cache_dir
+        .map(|dir| fetch::Fetcher::path_in(dir, key))
+        .filter(|cached| cached.is_file())
+        .map_or(path, |cached| cached.display().to_string())
+}
+
+/// Outcome of running a single part of a single day.
+#[derive(Debug)]
+pub struct RunReport {
+    pub key: Key,
+    pub part: u8,
+    /// Path of the input the part was run on.
+    pub path: String,
+    pub result: Result<String, RunError>,
+    /// Time spent parsing the input, or `None` if the input was not parsed
+    /// for this part, either because the part did not run or because it
+    /// reused the input parsed for an earlier part.
+    pub parse_elapsed: Option<Duration>,
+    /// Time spent solving the part, excluding any parsing.
+    pub elapsed: Duration,
+}
+
+impl RunReport {
+    /// Outputs whether the part ran and produced an answer, or was skipped
+    /// because it has not been solved yet.
+    #[must_use]
+    pub const fn is_success(&self) -> bool {
+        matches!(self.result, Ok(_) | Err(RunError::Unimplemented { .. }))
+    }
 
-    let output = match part {
-        1 => match day_number {
-            1 => days::day1::run1(&mut lines),
-            2 => days::day2::run1(&mut lines),
-            3 => days::day3::run1(&mut lines),
-            4 => days::day4::run1(&mut lines),
-            5 => days::day5::run1(&mut lines),
-            6 => days::day6::run1(&mut lines),
-            7 => days::day7::run1(&mut lines),
-            8 => days::day8::run1(&mut lines),
-            9 => days::day9::run1(&mut lines),
-            10 => days::day10::run1(&mut lines),
-            11 => days::day11::run1(&mut lines),
-            12 => days::day12::run1(&mut lines),
-            13 => days::day13::run1(&mut lines),
-            14 => days::day14::run1(&mut lines),
-            15 => days::day15::run1(&mut lines),
-            16 => days::day16::run1(&mut lines),
-            17 => days::day17::run1(&mut lines),
-            18 => days::day18::run1(&mut lines),
-            19 => days::day19::run1(&mut lines),
-            20 => days::day20::run1(&mut lines),
-            21 => days::day21::run1(&mut lines),
-            22 => days::day22::run1(&mut lines),
-            23 => days::day23::run1(&mut lines),
-            24 => days::day24::run1(&mut lines),
-            25 => days::day25::run1(&mut lines),
-
-            _ => panic!("huh?"),
-        },
-        2 => match day_number {
-            1 => days::day1::run2(&mut lines),
-            2 => days::day2::run2(&mut lines),
-            3 => days::day3::run2(&mut lines),
-            4 => days::day4::run2(&mut lines),
-            5 => days::day5::run2(&mut lines),
-            6 => days::day6::run2(&mut lines),
-            7 => days::day7::run2(&mut lines),
-            8 => days::day8::run2(&mut lines),
-            9 => days::day9::run2(&mut lines),
-            10 => days::day10::run2(&mut lines),
-            11 => days::day11::run2(&mut lines),
-            12 => days::day12::run2(&mut lines),
-            13 => days::day13::run2(&mut lines),
-            14 => days::day14::run2(&mut lines),
-            15 => days::day15::run2(&mut lines),
-            16 => days::day16::run2(&mut lines),
-            17 => days::day17::run2(&mut lines),
-            18 => days::day18::run2(&mut lines),
-            19 => days::day19::run2(&mut lines),
-            20 => days::day20::run2(&mut lines),
-            21 => days::day21::run2(&mut lines),
-            22 => days::day22::run2(&mut lines),
-            23 => days::day23::run2(&mut lines),
-            24 => days::day24::run2(&mut lines),
-            25 => days::day25::run2(&mut lines),
-            _ => panic!("huh?"),
-        },
-        _ => {
-            panic!("huh?");
+    /// Short description of the outcome, for display next to the answer.
+    #[must_use]
+    pub fn status(&self) -> String {
+        match &self.result {
+            Ok(_) => "ok".to_string(),
+            Err(RunError::Unimplemented { .. }) => "not implemented".to_string(),
+            Err(RunError::MissingInput { .. }) => "missing input".to_string(),
+            Err(RunError::SolverFailed { message, .. }) => format!("failed: {message}"),
+            Err(RunError::Panicked { message, .. }) => format!("panicked: {message}"),
+            Err(RunError::TimedOut { .. }) => "timed out".to_string(),
+            Err(err) => format!("error: {err}"),
         }
-    };
+    }
+
+    /// Formats the report as a single-line JSON object.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let (answer, error) = match &self.result {
+            Ok(answer) => (json_string(answer), "null".to_string()),
+            Err(err) => ("null".to_string(), json_string(&err.to_string())),
+        };
 
+        let parse_ms = self.parse_elapsed.map_or_else(
+            || "null".to_string(),
+            |elapsed| (elapsed.as_secs_f64() * 1e3).to_string(),
+        );
+
+        format!(
+            "{{\"year\":{},\"day\":{},\"part\":{},\"input\":{},\"answer\":{answer},\
+             \"parse_ms\":{parse_ms},\"duration_ms\":{},\"status\":{},\"error\":{error}}}",
+            self.key.year,
+            self.key.day,
+            self.part,
+            json_string(&self.path),
+            self.elapsed.as_secs_f64() * 1e3,
+            json_string(&self.status()),
+        )
+    }
+}
+
+/// Outputs the number of worker threads [`run_all`] is given by default: one
+/// per available core.
+#[must_use]
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Runs both parts of every registered day of `year` whose number lies in
+/// `days`, each on its default input (see [`input_path`]), parsed once per
+/// day, on a pool of `jobs` worker threads.
+///
+/// Each day is a single job, so the parts of a day still share its parsed
+/// input and run one after the other, while different days run concurrently.
+/// The reports come back ordered by day and part however the jobs finish, and
+/// each is timed on its own. Each part is also limited by `options` (see
+/// [`run_isolated`]).
+#[must_use]
+pub fn run_all(
+    year: u16,
+    days: RangeInclusive<u8>,
+    jobs: usize,
+    options: &RunOptions,
+) -> Vec<RunReport> {
+    let keys: Vec<Key> = days::registry()
+        .keys()
+        .filter(|key| key.year == year && days.contains(&key.day))
+        .collect();
+
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<Vec<RunReport>> = keys.iter().map(|_| Vec::new()).collect();
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs.clamp(1, keys.len().max(1)))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(&key) = keys.get(i) else {
+                            return done;
+                        };
+                        done.push((i, run_parts(key, &[1, 2], &input_path(key), options)));
+                    }
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            let done = worker
+                .join()
+                .unwrap_or_else(|payload| panic::resume_unwind(payload));
+            for (i, reports) in done {
+                results[i] = reports;
+            }
+        }
+    });
+
+    results.into_iter().flatten().collect()
+}
+
+/// Formats a duration with a unit suited to its magnitude.
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs >= 1.0 {
+        format!("{secs:.2} s")
+    } else if secs >= 1e-3 {
+        format!("{:.2} ms", secs * 1e3)
+    } else {
+        format!("{:.2} µs", secs * 1e6)
+    }
+}
+
+/// Formats a string as a JSON string literal, escaping it as needed.
+#[must_use]
+pub fn json_string(s: &str) -> String {
+    let mut output = String::with_capacity(s.len() + 2);
+    output.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(output, "\\u{:04x}", u32::from(c));
+            }
+            c => output.push(c),
+        }
+    }
+    output.push('"');
     output
 }
 
-fn read_lines<P>(filename: P) -> io::Result<LinesIterator>
+/// # Errors
+///
+/// Returns an error if the file cannot be opened.
+pub fn read_lines<P>(filename: P) -> io::Result<LinesIterator>
 where
     P: AsRef<Path>,
 {
     let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+    Ok(lines_from_reader(io::BufReader::new(file)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Answer;
+
+    /// Parses nothing, sleeps through part 1 and panics in part 2.
+    struct Stuck;
+
+    /// Set once part 1 of [`Stuck`] finds that it was given up on.
+    static STUCK_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+    impl Solution for Stuck {
+        fn name(&self) -> &'static str {
+            "Stuck"
+        }
+
+        fn day(&self) -> u8 {
+            1
+        }
+
+        fn year(&self) -> u16 {
+            2000
+        }
+
+        fn parse(&self, _: &mut LinesIterator) -> ParsedInput {
+            Box::new(())
+        }
+
+        fn part1(&self, _: &dyn Any, context: &Context) -> Answer {
+            thread::sleep(Duration::from_secs(1));
+            if context.progress.is_cancelled() {
+                STUCK_CANCELLED.store(true, Ordering::Relaxed);
+            }
+            Ok("late".to_string())
+        }
+
+        fn part2(&self, _: &dyn Any, _: &Context) -> Answer {
+            panic!("stuck in part {}", 2);
+        }
+    }
+
+    #[test]
+    fn test_run_isolated() {
+        let open = || Ok(lines_from_str(""));
+        let options = RunOptions {
+            timeout: Some(Duration::from_millis(50)),
+            ..RunOptions::default()
+        };
+
+        let attempt = run_isolated(&Stuck, 1, None, open, options.clone());
+        assert!(matches!(
+            attempt.result,
+            Err(RunError::TimedOut { part: 1, .. })
+        ));
+        assert!(attempt.input.is_none());
+
+        let attempt = run_isolated(&Stuck, 1, None, open, RunOptions::default());
+        assert_eq!(attempt.result.unwrap(), "late");
+        assert!(attempt.parse_elapsed.is_some());
+
+        let attempt = run_isolated(&Stuck, 2, attempt.input, open, options);
+        match attempt.result {
+            Err(RunError::Panicked { message, .. }) => assert_eq!(message, "stuck in part 2"),
+            result => panic!("{result:?}"),
+        }
+        assert!(attempt.parse_elapsed.is_none());
+        assert!(attempt.input.is_some());
+
+        // The part that timed out has stopped reporting its progress by the
+        // time it wakes up.
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !STUCK_CANCELLED.load(Ordering::Relaxed) && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(STUCK_CANCELLED.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_take_year() {
+        let args: Vec<String> = ["aoc", "1", "--year", "2023", "2"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let (rest, year) = take_year(&args).unwrap();
+        assert_eq!(rest, vec!["aoc", "1", "2"]);
+        assert_eq!(year, 2023);
+
+        let (_, year) = take_year(&rest).unwrap();
+        assert_eq!(year, DEFAULT_YEAR);
+
+        assert!(take_year(&args[..3]).is_err());
+        assert_eq!(
+            default_input_path(Key::new(2024, 5)),
+            "inputs/2024/day05.txt"
+        );
+    }
+
+    #[test]
+    fn test_input_path_falls_back_to_cache() {
+        let cache_dir =
+            std::env::temp_dir().join(format!("aoc_test_input_path_{}", std::process::id()));
+        std::fs::create_dir_all(cache_dir.join("2024")).unwrap();
+        std::fs::write(cache_dir.join("2024/day23.txt"), "").unwrap();
+        let cache = Some(cache_dir.as_path());
+
+        let key = Key::new(2024, 1);
+        assert_eq!(input_path_in(key, cache), default_input_path(key));
+        let key = Key::new(2024, 23);
+        assert_eq!(
+            input_path_in(key, cache),
+            cache_dir.join("2024/day23.txt").display().to_string()
+        );
+        assert_eq!(input_path_in(key, None), default_input_path(key));
+        let key = Key::new(2024, 24);
+        assert_eq!(input_path_in(key, cache), default_input_path(key));
+
+        std::fs::remove_dir_all(cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("5,60"), r#""5,60""#);
+        assert_eq!(json_string("a \"b\"\n\\"), r#""a \"b\"\n\\""#);
+        assert_eq!(json_string("\u{1}"), r#""\u0001""#);
+    }
+
+    #[test]
+    fn test_report_to_json() {
+        let report = RunReport {
+            key: Key::new(2024, 22),
+            part: 1,
+            path: "in.txt".to_string(),
+            result: Err(RunError::Unimplemented {
+                key: Key::new(2024, 22),
+                part: 1,
+            }),
+            parse_elapsed: None,
+            elapsed: Duration::from_millis(3),
+        };
+
+        assert_eq!(
+            report.to_json(),
+            "{\"year\":2024,\"day\":22,\"part\":1,\"input\":\"in.txt\",\"answer\":null,\
+             \"parse_ms\":null,\"duration_ms\":3,\"status\":\"not implemented\",\
+             \"error\":\"Part 1 of 2024 day 22 is not implemented\"}"
+        );
+    }
 }
@@ -1,25 +1,37 @@
-pub mod day1;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-pub mod day19;
-pub mod day2;
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
-pub mod day24;
-pub mod day25;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
+use crate::solution::Registry;
+use std::sync::OnceLock;
+
+/// Declares a function `register` adding the `Solver` of each listed day
+/// module of a year to a registry.
+macro_rules! register_days {
+    ($($day:ident),* $(,)?) => {
+        pub fn register(registry: &mut $crate::solution::Registry) {
+            $(registry.register(&$day::Solver);)*
+        }
+    };
+}
+
+/// Registers the days of each listed year module in the registry returned by
+/// [`registry`].
+macro_rules! register_years {
+    ($($year:ident),* $(,)?) => {
+        fn build_registry() -> Registry {
+            let mut registry = Registry::new();
+            $($year::register(&mut registry);)*
+            registry
+        }
+    };
+}
+
+// Declared after the macros so that the year modules can use them.
+pub mod y2024;
+
+register_years! {
+    y2024,
+}
+
+/// Outputs the registry containing the solution to every day of every year.
+pub fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(build_registry)
+}
@@ -0,0 +1,79 @@
+use crate::solution::Example;
+use crate::utils::LinesIterator;
+use std::collections::HashMap;
+
+crate::solution!(2024, 1, "Historian Hysteria");
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+",
+    part1: Some("11"),
+    part2: Some("31"),
+    params: &[],
+}];
+
+/// Pairs of numbers from the left and right lists.
+pub type Input = Vec<(i64, i64)>;
+
+/// # Panics
+///
+/// idfk
+pub fn parse(lines: &mut LinesIterator) -> Input {
+    lines
+        .map(|temp_line| {
+            let line = temp_line.unwrap();
+            let mut nums = line.split_whitespace().map(|x| x.parse::<i64>().unwrap());
+            (nums.next().unwrap(), nums.next().unwrap())
+        })
+        .collect()
+}
+
+#[must_use]
+pub fn run1(input: &Input) -> String {
+    let mut l: Vec<i64> = input.iter().map(|&(l, _)| l).collect();
+    let mut r: Vec<i64> = input.iter().map(|&(_, r)| r).collect();
+
+    l.sort_unstable();
+    r.sort_unstable();
+
+    let mut total_dist = 0;
+
+    for i in 0..l.len() {
+        total_dist += (l[i] - r[i]).abs();
+    }
+
+    format!("{total_dist}")
+}
+
+#[must_use]
+pub fn run2(input: &Input) -> String {
+    let mut lcount: HashMap<i64, i64> = HashMap::new();
+    let mut rcount: HashMap<i64, i64> = HashMap::new();
+
+    for &(l, r) in input {
+        match rcount.get(&r) {
+            Some(&x) => rcount.insert(r, x + 1),
+            None => rcount.insert(r, 1),
+        };
+        match lcount.get(&l) {
+            Some(&x) => lcount.insert(l, &x + 1),
+            None => lcount.insert(l, 1),
+        };
+    }
+
+    let mut output = 0;
+
+    for key in lcount.keys() {
+        if rcount.contains_key(key) {
+            output += *key * lcount[key] * rcount[key];
+        }
+    }
+
+    format!("{output}")
+}
@@ -1,6 +1,25 @@
-use crate::graph::{num_paths, num_reachable_targets};
+use crate::graph::{count_paths, num_reachable_targets};
+use crate::solution::Example;
 use crate::utils::{lines_to_grid_of_usize, LinesIterator};
 
+crate::solution!(2024, 10, "Hoof It");
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+",
+    part1: Some("36"),
+    part2: Some("81"),
+    params: &[],
+}];
+
 fn make_get_edges(map: &[Vec<usize>]) -> impl Fn((usize, usize)) -> Vec<(usize, usize)> + '_ {
     let height = map.len();
     let width = map[0].len();
@@ -34,11 +53,16 @@ fn make_is_9(map: &[Vec<usize>]) -> impl Fn((usize, usize)) -> bool + '_ {
     |t: (usize, usize)| map[t.0][t.1] == 9
 }
 
-pub fn run1(lines: &mut LinesIterator) -> String {
-    let map: Vec<_> = lines_to_grid_of_usize(lines).collect();
+pub type Input = Vec<Vec<usize>>;
+
+pub fn parse(lines: &mut LinesIterator) -> Input {
+    lines_to_grid_of_usize(lines).collect()
+}
 
-    let get_edges = make_get_edges(&map);
-    let is_9 = make_is_9(&map);
+#[must_use]
+pub fn run1(map: &Input) -> String {
+    let get_edges = make_get_edges(map);
+    let is_9 = make_is_9(map);
 
     let mut output = 0;
 
@@ -53,18 +77,19 @@ pub fn run1(lines: &mut LinesIterator) -> String {
     format!("{output}")
 }
 
-pub fn run2(lines: &mut LinesIterator) -> String {
-    let map: Vec<_> = lines_to_grid_of_usize(lines).collect();
-
-    let mut get_edges = make_get_edges(&map);
-    let is_9 = make_is_9(&map);
+/// # Panics
+#[must_use]
+pub fn run2(map: &Input) -> String {
+    let get_edges = make_get_edges(map);
+    let is_9 = make_is_9(map);
 
     let mut output = 0;
 
     for (r, row) in map.iter().enumerate() {
         for (c, character) in row.iter().enumerate() {
             if *character == 0 {
-                output += num_paths((r, c), &is_9, &mut get_edges);
+                output += count_paths((r, c), &is_9, &get_edges)
+                    .expect("heights only increase along a trail");
             }
         }
     }
@@ -1,7 +1,25 @@
 use crate::memoizer::Memoizer;
+use crate::solution::{Answer, Context, Example, Param};
 use crate::utils::LinesIterator;
 use std::collections::HashMap;
 
+crate::solution!(2024, 11, "Plutonian Pebbles", params);
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+125 17
+",
+    part1: Some("55312"),
+    part2: Some("55312"),
+    params: &[("gens", "25")],
+}];
+
+pub const PARAMS: &[Param] = &[Param {
+    name: "gens",
+    default: "75",
+    description: "number of times the stones blink in part 2",
+}];
+
 fn num_digits(n: u128) -> usize {
     (n.checked_ilog10().unwrap_or(0) + 1) as usize
 }
@@ -31,20 +49,27 @@ fn blink(stones: &[u128]) -> Vec<u128> {
         .collect()
 }
 
+pub type Input = Vec<u128>;
+
 /// # Panics
-pub fn run1(lines: &mut LinesIterator) -> String {
-    let mut nums: Vec<u128> = lines
+pub fn parse(lines: &mut LinesIterator) -> Input {
+    lines
         .next()
         .unwrap()
         .unwrap()
         .split_whitespace()
         .map(str::parse::<u128>)
         .map(Result::unwrap)
-        .collect();
+        .collect()
+}
 
-    for _ in 0..25 {
+#[must_use]
+pub fn run1(nums: &Input) -> String {
+    let mut nums = nums.clone();
+
+    for i in 0..25 {
         let x = blink(&nums);
-        // println!("{} {}", i + 1, x.len());
+        crate::trace!("{} stones after {} blinks", x.len(), i + 1);
         nums = x;
     }
 
@@ -98,25 +123,18 @@ impl StoneCollapser {
     }
 }
 
-/// # Panics
-pub fn run2(lines: &mut LinesIterator) -> String {
-    let nums: Vec<u128> = lines
-        .next()
-        .unwrap()
-        .unwrap()
-        .split_whitespace()
-        .map(str::parse::<u128>)
-        .map(Result::unwrap)
-        .collect();
-
+/// # Errors
+///
+/// Returns an error if the number of blinks is not an integer.
+pub fn run2(nums: &Input, context: &Context) -> Answer {
     let mut sc = StoneCollapser::new();
     let mut result = 0;
 
-    let gens = 75;
+    let gens: usize = context.param("gens")?;
 
-    for stone in nums {
+    for &stone in nums {
         result += sc.collapse_stone(stone, gens);
     }
 
-    format!("{result}")
+    Ok(format!("{result}"))
 }
@@ -1,8 +1,29 @@
 use crate::direction::{Coords, Direction};
+use crate::solution::Example;
 use crate::uptree::UpTree;
 use crate::utils::{lines_to_grid_of_chars, LinesIterator};
 use std::collections::{HashSet, VecDeque};
 
+crate::solution!(2024, 12, "Garden Groups");
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+",
+    part1: Some("1930"),
+    part2: Some("1206"),
+    params: &[],
+}];
+
 type Nbr = (Direction, Coords);
 type NbrsGrid = Vec<Vec<Nbrs>>;
 type RegionsList = Vec<Vec<Coords>>;
@@ -141,12 +162,18 @@ fn get_components(grid: &[Vec<char>]) -> (NbrsGrid, RegionsList) {
     )
 }
 
+pub type Input = Vec<Vec<char>>;
+
+pub fn parse(lines: &mut LinesIterator) -> Input {
+    lines_to_grid_of_chars(lines).collect()
+}
+
 /// # Panics
 ///
 /// stfu
-pub fn run1(lines: &mut LinesIterator) -> String {
-    let grid: Vec<_> = lines_to_grid_of_chars(lines).collect();
-    let (filtered_nbrs_grid, regions) = get_components(&grid);
+#[must_use]
+pub fn run1(grid: &Input) -> String {
+    let (filtered_nbrs_grid, regions) = get_components(grid);
 
     let mut output = 0;
 
@@ -165,10 +192,9 @@ pub fn run1(lines: &mut LinesIterator) -> String {
 /// # Panics
 ///
 /// stfu
-pub fn run2(lines: &mut LinesIterator) -> String {
-    let grid: Vec<_> = lines_to_grid_of_chars(lines).collect();
-
-    let (nbrs, regions) = get_components(&grid);
+#[must_use]
+pub fn run2(grid: &Input) -> String {
+    let (nbrs, regions) = get_components(grid);
     let mut output = 0;
 
     for region in regions {
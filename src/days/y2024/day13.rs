@@ -1,21 +1,57 @@
 use crate::graph::shortest_path_cost;
+use crate::solution::{Context, Example};
 use crate::utils::LinesIterator;
 
+crate::solution!(2024, 13, "Claw Contraption");
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279
+",
+    part1: Some("480"),
+    part2: None,
+    params: &[],
+}];
+
 #[derive(Debug)]
-struct Machine {
+pub struct Machine {
     a: (u64, u64),
     b: (u64, u64),
     prize: (u64, u64),
 }
 
-const A_COST: usize = 3;
-const B_COST: usize = 1;
+const A_COST: u64 = 3;
+const B_COST: u64 = 1;
 
 impl Machine {
     const fn new(a: (u64, u64), b: (u64, u64), prize: (u64, u64)) -> Self {
         Self { a, b, prize }
     }
 
+    /// Outputs the same machine with its prize moved by [`PRIZE_OFFSET`] along
+    /// both axes.
+    const fn with_prize_offset(&self) -> Self {
+        Self::new(
+            self.a,
+            self.b,
+            (self.prize.0 + PRIZE_OFFSET, self.prize.1 + PRIZE_OFFSET),
+        )
+    }
+
     fn cost(&self) -> Option<u64> {
         let mut get_edges = |coords: (u64, u64)| {
             let mut output = Vec::new();
@@ -35,7 +71,7 @@ impl Machine {
             output
         };
 
-        shortest_path_cost((0, 0), |x| x == self.prize, &mut get_edges).map(|x| x as u64)
+        shortest_path_cost((0, 0), |x| x == self.prize, &mut get_edges)
     }
 
     /// Write A = [a.0, b.0 ; a.1, b.1] and p = (prize.0, prize.1). Then a
@@ -107,7 +143,7 @@ impl Machine {
                 let n1 = m1 / det;
                 let n2 = m2 / det;
                 if n1 >= 0 && n2 >= 0 {
-                    output = Some((n1 as u64 * A_COST as u64) + (n2 as u64 * B_COST as u64));
+                    output = Some((n1 as u64 * A_COST) + (n2 as u64 * B_COST));
                 }
             }
         }
@@ -115,34 +151,13 @@ impl Machine {
     }
 }
 
-fn parse_input(lines: &mut LinesIterator) -> Vec<Machine> {
-    let mut output = Vec::new();
-
-    loop {
-        let a_line = lines.next().unwrap().unwrap();
-        let a1 = a_line[12..14].parse::<u64>().unwrap();
-        let a2 = a_line[18..20].parse::<u64>().unwrap();
-
-        let b_line = lines.next().unwrap().unwrap();
-        let b1 = b_line[12..14].parse::<u64>().unwrap();
-        let b2 = b_line[18..20].parse::<u64>().unwrap();
-
-        let p_line = &lines.next().unwrap().unwrap()[9..];
-        let mut p_line = p_line.split(", ");
-        let p1 = p_line.next().unwrap().parse::<u64>().unwrap();
-        let p2 = p_line.next().unwrap()[2..].parse::<u64>().unwrap();
-
-        output.push(Machine::new((a1, a2), (b1, b2), (p1, p2)));
-
-        if lines.next().is_none() {
-            break;
-        }
-    }
+/// Distance by which the prizes are further away in part 2.
+const PRIZE_OFFSET: u64 = 10_000_000_000_000;
 
-    output
-}
+pub type Input = Vec<Machine>;
 
-fn parse_input_2(lines: &mut LinesIterator) -> Vec<Machine> {
+/// # Panics
+pub fn parse(lines: &mut LinesIterator) -> Input {
     let mut output = Vec::new();
 
     loop {
@@ -159,11 +174,7 @@ fn parse_input_2(lines: &mut LinesIterator) -> Vec<Machine> {
         let p1 = p_line.next().unwrap().parse::<u64>().unwrap();
         let p2 = p_line.next().unwrap()[2..].parse::<u64>().unwrap();
 
-        output.push(Machine::new(
-            (a1, a2),
-            (b1, b2),
-            (p1 + 10_000_000_000_000, p2 + 10_000_000_000_000),
-        ));
+        output.push(Machine::new((a1, a2), (b1, b2), (p1, p2)));
 
         if lines.next().is_none() {
             break;
@@ -173,14 +184,25 @@ fn parse_input_2(lines: &mut LinesIterator) -> Vec<Machine> {
     output
 }
 
-pub fn run1(lines: &mut LinesIterator) -> String {
-    let output: u64 = parse_input(lines).iter().filter_map(Machine::cost).sum();
+pub fn run1(machines: &Input, context: &Context) -> String {
+    let output: u64 = machines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, machine)| {
+            context.progress.update(i, machines.len());
+            machine.cost()
+        })
+        .sum();
 
     format!("{output}")
 }
 
-pub fn run2(lines: &mut LinesIterator) -> String {
-    let output: u64 = parse_input_2(lines).iter().filter_map(Machine::cost2).sum();
+pub fn run2(machines: &Input) -> String {
+    let output: u64 = machines
+        .iter()
+        .map(Machine::with_prize_offset)
+        .filter_map(|machine| machine.cost2())
+        .sum();
 
     format!("{output}")
 }
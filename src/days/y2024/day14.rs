@@ -0,0 +1,236 @@
+use crate::solution::{Answer, Context, Example, Param, SolveError};
+use crate::utils::LinesIterator;
+use std::cmp::Ordering::{Greater, Less};
+
+crate::solution!(2024, 14, "Restroom Redoubt", params);
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3",
+    part1: Some("12"),
+    part2: None,
+    params: &[("height", "7"), ("width", "11")],
+}];
+
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "height",
+        default: "103",
+        description: "number of rows of tiles in the room",
+    },
+    Param {
+        name: "width",
+        default: "101",
+        description: "number of columns of tiles in the room",
+    },
+];
+
+pub type Coords = (i32, i32);
+pub type Velocity = (i32, i32);
+
+/// Position and velocity of each robot.
+pub type Input = Vec<(Coords, Velocity)>;
+
+/// # Panics
+pub fn parse(lines: &mut LinesIterator) -> Input {
+    let mut output = Vec::new();
+    for line in lines {
+        let line = line.unwrap();
+        let mut line = line.split(' ');
+
+        let mut pos = line.next().unwrap()[2..].split(',');
+        let p2 = pos.next().unwrap().parse::<i32>().unwrap();
+        let p1 = pos.next().unwrap().parse::<i32>().unwrap();
+
+        let mut vel = line.next().unwrap()[2..].split(',');
+        let v2 = vel.next().unwrap().parse::<i32>().unwrap();
+        let v1 = vel.next().unwrap().parse::<i32>().unwrap();
+
+        output.push(((p1, p2), (v1, v2)));
+    }
+
+    output
+}
+
+const fn update(
+    robot: (Coords, Velocity),
+    seconds: i32,
+    height: i32,
+    width: i32,
+) -> (Coords, Velocity) {
+    let p1 = robot.0 .0;
+    let p2 = robot.0 .1;
+
+    let v1 = robot.1 .0;
+    let v2 = robot.1 .1;
+
+    let new_p1 = (p1 + v1 * seconds).rem_euclid(height);
+    let new_p2 = (p2 + v2 * seconds).rem_euclid(width);
+
+    ((new_p1, new_p2), (v1, v2))
+}
+
+/// Outputs the height and width of the room, checking that every robot is
+/// inside it.
+fn room_size(robots: &Input, context: &Context) -> Result<(i32, i32), SolveError> {
+    let height: i32 = context.param("height")?;
+    let width: i32 = context.param("width")?;
+
+    if height <= 0 || width <= 0 {
+        return Err(SolveError::Failed(format!(
+            "the room cannot be {height} by {width} tiles"
+        )));
+    }
+    if let Some(((r, c), _)) = robots
+        .iter()
+        .find(|((r, c), _)| !(0..height).contains(r) || !(0..width).contains(c))
+    {
+        return Err(SolveError::Failed(format!(
+            "robot at {c},{r} is outside the room"
+        )));
+    }
+
+    Ok((height, width))
+}
+
+/// # Errors
+///
+/// Returns an error if the size of the room is not a pair of positive integers
+/// or some robot is outside it.
+pub fn run1(robots: &Input, context: &Context) -> Answer {
+    let (height, width) = room_size(robots, context)?;
+
+    let mut top_left = 0;
+    let mut top_right = 0;
+    let mut bottom_left = 0;
+    let mut bottom_right = 0;
+
+    for &robot in robots {
+        let height_middle = (height - 1) / 2;
+        let width_middle = (width - 1) / 2;
+
+        let (p1, p2) = update(robot, 100, height, width).0;
+
+        match (p1.cmp(&height_middle), p2.cmp(&width_middle)) {
+            (Less, Less) => top_left += 1,
+            (Less, Greater) => top_right += 1,
+            (Greater, Less) => bottom_left += 1,
+            (Greater, Greater) => bottom_right += 1,
+            _ => {}
+        }
+    }
+
+    let output = top_left * top_right * bottom_left * bottom_right;
+
+    Ok(format!("{output}"))
+}
+
+fn robots_to_grid(robots: &Vec<(Coords, Velocity)>, height: usize, width: usize) -> Vec<Vec<char>> {
+    let mut chars = Vec::with_capacity(height);
+
+    for _ in 0..height {
+        chars.push(vec![' '; width]);
+    }
+
+    for ((r, c), _) in robots {
+        chars[*r as usize][*c as usize] = '█';
+    }
+
+    chars
+}
+
+/// # Errors
+///
+/// Returns an error if the size of the room is not a pair of positive integers,
+/// the room is too low to hold the tree, some robot is outside it, or the
+/// robots never form the tree.
+pub fn run2(robots: &Input, context: &Context) -> Answer {
+    let (height, width) = room_size(robots, context)?;
+    if height < 10 {
+        return Err(SolveError::Failed(format!(
+            "a room {height} tiles high cannot hold the tree"
+        )));
+    }
+    let (rows, cols) = (
+        height.unsigned_abs() as usize,
+        width.unsigned_abs() as usize,
+    );
+
+    let mut robots = robots.clone();
+
+    // Every robot is back where it started after `rows * cols` seconds.
+    for n in 0..rows * cols {
+        let chars = robots_to_grid(&robots, rows, cols);
+
+        for (i, row) in chars.iter().enumerate() {
+            for (j, c) in row.iter().enumerate() {
+                if i + 9 < rows
+                    && *c == '█'
+                    && chars[i + 1][j] == '█'
+                    && chars[i + 2][j] == '█'
+                    && chars[i + 3][j] == '█'
+                    && chars[i + 4][j] == '█'
+                    && chars[i + 5][j] == '█'
+                    && chars[i + 6][j] == '█'
+                    && chars[i + 7][j] == '█'
+                    && chars[i + 8][j] == '█'
+                    && chars[i + 9][j] == '█'
+                {
+                    return Ok(format!("{n}"));
+                }
+            }
+        }
+
+        robots = robots
+            .into_iter()
+            .map(|r| update(r, 1, height, width))
+            .collect();
+    }
+
+    Err(SolveError::Failed(
+        "the robots never form the tree".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::lines_from_str;
+
+    #[test]
+    fn test_run2_fails_in_small_rooms() {
+        let example = &EXAMPLES[0];
+        let robots = parse(&mut lines_from_str(example.input));
+        let values: Vec<(String, String)> = example
+            .params
+            .iter()
+            .map(|&(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        let context = Context::new(PARAMS).with_values(&values);
+        assert!(matches!(
+            run2(&robots, &context),
+            Err(SolveError::Failed(_))
+        ));
+
+        let values = [
+            ("height".to_string(), "11".to_string()),
+            ("width".to_string(), "11".to_string()),
+        ];
+        let context = Context::new(PARAMS).with_values(&values);
+        assert!(matches!(
+            run2(&robots, &context),
+            Err(SolveError::Failed(_))
+        ));
+    }
+}
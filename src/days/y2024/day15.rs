@@ -1,8 +1,59 @@
 use crate::direction::{Coords, Direction};
+use crate::solution::Example;
 use crate::utils::LinesIterator;
 use std::collections::{HashSet, VecDeque};
 use std::fmt::Display;
 
+crate::solution!(2024, 15, "Warehouse Woes");
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: "\
+##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
+",
+        part1: Some("10092"),
+        part2: Some("9021"),
+        params: &[],
+    },
+    Example {
+        input: "\
+########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<
+",
+        part1: Some("2028"),
+        part2: None,
+        params: &[],
+    },
+];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Tile {
     Wall,
@@ -34,7 +85,7 @@ impl Tile {
 }
 
 #[derive(Debug, Clone)]
-struct Warehouse {
+pub struct Warehouse {
     grid: Vec<Vec<Tile>>,
     robot: Coords,
 }
@@ -159,7 +210,10 @@ impl Display for Warehouse {
     }
 }
 
-fn parse_input(lines: &mut LinesIterator) -> (Warehouse, Vec<Direction>) {
+/// The warehouse along with the moves the robot attempts.
+pub type Input = (Warehouse, Vec<Direction>);
+
+pub fn parse(lines: &mut LinesIterator) -> Input {
     // let mut prev_line = lines.next().unwrap().unwrap();
     let mut grid = Vec::new();
     let mut found = false;
@@ -205,10 +259,11 @@ fn parse_input(lines: &mut LinesIterator) -> (Warehouse, Vec<Direction>) {
     )
 }
 
-pub fn run1(lines: &mut LinesIterator) -> String {
-    let (mut wh, steps) = parse_input(lines);
+#[must_use]
+pub fn run1((wh, steps): &Input) -> String {
+    let mut wh = wh.clone();
 
-    for dir in steps {
+    for &dir in steps {
         wh.step(dir);
     }
 
@@ -437,11 +492,11 @@ fn warehouse_to_wide_warehouse(wh: &Warehouse) -> WideWarehouse {
     }
 }
 
-pub fn run2(lines: &mut LinesIterator) -> String {
-    let (wh, steps) = parse_input(lines);
-    let mut wwh = warehouse_to_wide_warehouse(&wh);
+#[must_use]
+pub fn run2((wh, steps): &Input) -> String {
+    let mut wwh = warehouse_to_wide_warehouse(wh);
 
-    for dir in steps {
+    for &dir in steps {
         wwh.step(dir);
     }
 
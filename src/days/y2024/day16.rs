@@ -1,10 +1,62 @@
 use crate::direction::{Coords, Direction};
-use crate::graph::{get_nodes_in_cheapest_paths, shortest_path_cost};
+use crate::graph::{astar_cost, get_nodes_in_cheapest_paths, manhattan};
 use crate::memoizer::Memoizer;
+use crate::solution::{Answer, Example, SolveError};
 use crate::utils::LinesIterator;
 use std::collections::HashSet;
 use std::hash::Hash;
 
+crate::solution!(2024, 16, "Reindeer Maze");
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: "\
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############
+",
+        part1: Some("7036"),
+        part2: Some("45"),
+        params: &[],
+    },
+    Example {
+        input: "\
+#################
+#...#...#...#..E#
+#.#.#.#.#.#.#.#.#
+#.#.#.#...#...#.#
+#.#.#.#.###.#.#.#
+#...#.#.#.....#.#
+#.#.#.#.#.#####.#
+#.#...#.#.#.....#
+#.#.#####.#.###.#
+#.#.#.......#...#
+#.#.###.#####.###
+#.#.#...#.....#.#
+#.#.#.#####.###.#
+#.#.#.........#.#
+#.#.#.#########.#
+#S#.............#
+#################
+",
+        part1: Some("11048"),
+        part2: Some("64"),
+        params: &[],
+    },
+];
+
 const FWD_COST: usize = 1;
 const TURN_COST: usize = 1000;
 
@@ -12,7 +64,7 @@ const TURN_COST: usize = 1000;
 struct Pos(Coords, Direction);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Tile {
+pub enum Tile {
     Start,
     End,
     Empty,
@@ -89,42 +141,52 @@ impl Tile {
     // }
 }
 
-pub fn run1(lines: &mut LinesIterator) -> String {
-    let grid: Vec<Vec<Tile>> = lines
+pub type Input = Vec<Vec<Tile>>;
+
+/// # Panics
+pub fn parse(lines: &mut LinesIterator) -> Input {
+    lines
         .map(Result::unwrap)
         .map(|v| v.chars().map(Tile::from).collect())
-        .collect();
+        .collect()
+}
 
+/// # Errors
+///
+/// Returns an error if there is no path from the start to the end.
+pub fn run1(grid: &Input) -> Answer {
     let height = grid.len();
     let width = grid.len();
 
     let src = Pos((height - 2, 1), Direction::E);
-    let is_tgt = |p: Pos| p.0 == (1, width - 2);
+    let tgt = (1, width - 2);
+    let is_tgt = |p: Pos| p.0 == tgt;
 
-    let mut get_children_memoizer = Memoizer::new(make_get_children(&grid));
+    let mut get_children_memoizer = Memoizer::new(make_get_children(grid));
     let mut get_children = |x| get_children_memoizer.call(x);
 
-    let output = shortest_path_cost(src, is_tgt, &mut get_children);
+    // Every step towards the end costs at least this much, however the reindeer
+    // turns.
+    let heuristic = |p: Pos| manhattan(p.0, tgt) * FWD_COST;
+    let output = astar_cost(src, &is_tgt, &mut get_children, &heuristic);
 
-    output.map_or_else(|| "No path found".to_string(), |cost| format!("{cost}"))
+    output
+        .map(|cost| format!("{cost}"))
+        .ok_or_else(|| SolveError::Failed("No path found".to_string()))
 }
 
 /// # Panics
-pub fn run2(lines: &mut LinesIterator) -> String {
-    let grid: Vec<Vec<Tile>> = lines
-        .map(Result::unwrap)
-        .map(|v| v.chars().map(Tile::from).collect())
-        .collect();
-
+#[must_use]
+pub fn run2(grid: &Input) -> String {
     let height = grid.len();
     let width = grid.len();
 
     let src = Pos((height - 2, 1), Direction::E);
 
-    let mut get_children_memoizer = Memoizer::new(make_get_children(&grid));
+    let mut get_children_memoizer = Memoizer::new(make_get_children(grid));
     let mut get_children = |x| get_children_memoizer.call(x);
 
-    let mut get_parents_memoizer = Memoizer::new(make_get_parents(&grid));
+    let mut get_parents_memoizer = Memoizer::new(make_get_parents(grid));
     let mut get_parents = |x| get_parents_memoizer.call(x);
 
     let mut is_tgt = |x: Pos| x.0 == (1, width - 2);
@@ -1,7 +1,23 @@
 use crate::graph::shortest_path_multiple_tgts;
+use crate::solution::{Answer, Example, SolveError};
 use crate::utils::LinesIterator;
 use std::fmt::Write;
 
+crate::solution!(2024, 17, "Chronospatial Computer");
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0
+",
+    part1: Some("4,6,3,5,6,3,5,2,1,0"),
+    part2: None,
+    params: &[],
+}];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Output {
     None,
@@ -20,7 +36,7 @@ impl Output {
 }
 
 #[derive(Debug, Clone)]
-struct Computer {
+pub struct Computer {
     a: usize,
     b: usize,
     c: usize,
@@ -39,30 +55,32 @@ impl Computer {
         }
     }
 
-    // fn print_program(&self) {
-    //     for (i, opcode) in self.program.iter().enumerate().filter(|(i, _)| *i % 2 == 0) {
-    //         match *opcode {
-    //             // adv: Divide A by 2.pow(combo), store to A (truncate).
-    //             0 => println!("adv {}", self.program[i + 1]),
-    //             // bxl: Bitwise XOR B with literal, store to B.
-    //             1 => println!("bxl {}", self.program[i + 1]),
-    //             // bst: B = combo modulo 8.
-    //             2 => println!("bst {}", self.program[i + 1]),
-    //             // jnz: Do nothing if A == 0, otherwise jump to operand literal.
-    //             // Only increment instruction pointer if doesn't jump.
-    //             3 => println!("jnz {}", self.program[i + 1]),
-    //             // bxc: B = B XOR C.
-    //             4 => println!("bxc"),
-    //             // out: Output combo operand.
-    //             5 => println!("out {}", self.program[i + 1]),
-    //             // bdv: adv but store to B instead of A.
-    //             6 => println!("bdv {}", self.program[i + 1]),
-    //             // cdv: adv but store to B instead of A.
-    //             7 => println!("cdv {}", self.program[i + 1]),
-    //             _ => panic!("Invalid opcode"),
-    //         }
-    //     }
-    // }
+    /// Lists the instructions of the program, one per line.
+    fn disassemble(&self) -> String {
+        let mut output = String::new();
+        for instruction in self.program.chunks(2) {
+            let name = match instruction[0] {
+                0 => "adv",
+                1 => "bxl",
+                2 => "bst",
+                3 => "jnz",
+                4 => "bxc",
+                5 => "out",
+                6 => "bdv",
+                7 => "cdv",
+                _ => panic!("Invalid opcode"),
+            };
+            match instruction.get(1) {
+                Some(operand) if instruction[0] != 4 => {
+                    let _ = writeln!(output, "{name} {operand}");
+                }
+                _ => {
+                    let _ = writeln!(output, "{name}");
+                }
+            }
+        }
+        output
+    }
 
     /// Returns true if the program halts.
     #[allow(clippy::cast_possible_truncation)]
@@ -77,21 +95,21 @@ impl Computer {
         match opcode {
             // adv: Divide A by 2.pow(combo), store to A (truncate).
             0 => {
-                // println!("adv {operand} (combo: {})", self.combo(operand));
+                crate::trace!("adv {operand} (combo: {})", self.combo(operand));
                 self.a >>= self.combo(operand);
                 self.ip += 2;
             }
 
             // bxl: Bitwise XOR B with literal, store to B.
             1 => {
-                // println!("bxl {operand}");
+                crate::trace!("bxl {operand}");
                 self.b ^= operand;
                 self.ip += 2;
             }
 
             // bst: B = combo modulo 8.
             2 => {
-                // println!("bst {operand} (combo: {})", self.combo(operand));
+                crate::trace!("bst {operand} (combo: {})", self.combo(operand));
                 self.b = self.combo(operand) & 7;
                 self.ip += 2;
             }
@@ -99,7 +117,7 @@ impl Computer {
             // jnz: Do nothing if A == 0, otherwise jump to operand literal.
             // Only increment instruction pointer if doesn't jump.
             3 => {
-                // println!("jnz {operand}\n");
+                crate::trace!("jnz {operand}");
                 if self.a != 0 {
                     self.ip = operand;
                 } else {
@@ -109,28 +127,28 @@ impl Computer {
 
             // bxc: B = B XOR C.
             4 => {
-                // println!("bxc");
+                crate::trace!("bxc");
                 self.b ^= self.c;
                 self.ip += 2;
             }
 
             // out: Output combo operand.
             5 => {
-                // println!("out {operand} (combo: {})", self.combo(operand));
+                crate::trace!("out {operand} (combo: {})", self.combo(operand));
                 self.ip += 2;
                 return Output::Some(self.combo(operand) & 7);
             }
 
             // bdv: adv but store to B instead of A.
             6 => {
-                // println!("bdv {operand} (combo: {})", self.combo(operand));
+                crate::trace!("bdv {operand} (combo: {})", self.combo(operand));
                 self.b = self.a >> self.combo(operand);
                 self.ip += 2;
             }
 
             // cdv: adv but store to B instead of A.
             7 => {
-                // println!("cdv {operand} (combo: {})", self.combo(operand));
+                crate::trace!("cdv {operand} (combo: {})", self.combo(operand));
                 self.c = self.a >> self.combo(operand);
                 self.ip += 2;
             }
@@ -160,7 +178,11 @@ impl Computer {
     }
 }
 
-fn parse_input(lines: &mut LinesIterator) -> Computer {
+/// The computer in its initial state, with the program loaded.
+pub type Input = Computer;
+
+/// # Panics
+pub fn parse(lines: &mut LinesIterator) -> Input {
     let a = lines.next().unwrap().unwrap()[12..]
         .parse::<usize>()
         .unwrap();
@@ -199,8 +221,10 @@ fn my_prgrm(a: usize) -> Vec<usize> {
     output
 }
 
-pub fn run1(lines: &mut LinesIterator) -> String {
-    let mut comp = parse_input(lines);
+#[must_use]
+pub fn run1(comp: &Input) -> String {
+    let mut comp = comp.clone();
+    crate::debug!("program:\n{}", comp.disassemble());
 
     let result =
         comp.run()
@@ -214,8 +238,12 @@ pub fn run1(lines: &mut LinesIterator) -> String {
     result[..result.len() - 1].to_string()
 }
 
-pub fn run2(lines: &mut LinesIterator) -> String {
-    let program = parse_input(lines).program;
+/// # Errors
+///
+/// Returns an error if no initial value of register A makes the program
+/// output itself.
+pub fn run2(comp: &Input) -> Answer {
+    let program = &comp.program;
 
     let get_children = |x: (usize, usize)| {
         let mut output: Vec<(usize, usize)> = Vec::new();
@@ -232,10 +260,9 @@ pub fn run2(lines: &mut LinesIterator) -> String {
         output
     };
 
-    let is_tgt = |x: (usize, usize)| program == my_prgrm(x.0);
+    let is_tgt = |x: (usize, usize)| *program == my_prgrm(x.0);
 
-    shortest_path_multiple_tgts((0, program.len()), is_tgt, get_children).map_or_else(
-        || "No input will result in program as output".to_string(),
-        |path| format!("{}", path[path.len() - 1].0),
-    )
+    shortest_path_multiple_tgts((0, program.len()), is_tgt, get_children)
+        .map(|path| format!("{}", path[path.len() - 1].0))
+        .ok_or_else(|| SolveError::Failed("No input will result in program as output".to_string()))
 }
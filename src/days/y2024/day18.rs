@@ -0,0 +1,176 @@
+use crate::direction::{Coords, Direction};
+use crate::graph::{exists_path, shortest_path_length};
+use crate::solution::{Answer, Context, Example, Param, SolveError};
+use crate::utils::LinesIterator;
+
+crate::solution!(2024, 18, "RAM Run", params);
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0",
+    part1: Some("22"),
+    part2: Some("6,1"),
+    params: &[("size", "7"), ("bytes", "12")],
+}];
+
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "size",
+        default: "71",
+        description: "number of rows and of columns of the memory space",
+    },
+    Param {
+        name: "bytes",
+        default: "1024",
+        description: "number of bytes fallen before the shortest path is found",
+    },
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Tile {
+    Free,
+    Corrupted,
+}
+
+/// Positions of the falling bytes, in the order they fall.
+pub type Input = Vec<Coords>;
+
+/// # Panics
+pub fn parse(lines: &mut LinesIterator) -> Input {
+    let mut output = Vec::new();
+
+    for line in lines {
+        let line = line.unwrap();
+        let mut parts = line.split(',');
+        let c = parts.next().unwrap().parse::<usize>().unwrap();
+        let r = parts.next().unwrap().parse::<usize>().unwrap();
+
+        output.push((r, c));
+    }
+
+    output
+}
+
+/// Outputs the size of the memory space and the number of bytes fallen before
+/// the shortest path is found, checking that they fit the bytes.
+fn memory_space(bytes: &Input, context: &Context) -> Result<(usize, usize), SolveError> {
+    let size: usize = context.param("size")?;
+    let fallen: usize = context.param("bytes")?;
+
+    if size == 0 {
+        return Err(SolveError::Failed("the memory space is empty".to_string()));
+    }
+    if fallen > bytes.len() {
+        return Err(SolveError::Failed(format!(
+            "only {} bytes fall, not {fallen}",
+            bytes.len()
+        )));
+    }
+    if let Some((r, c)) = bytes.iter().find(|&&(r, c)| r >= size || c >= size) {
+        return Err(SolveError::Failed(format!(
+            "byte {c},{r} falls outside the memory space"
+        )));
+    }
+
+    Ok((size, fallen))
+}
+
+/// # Errors
+///
+/// Returns an error if the parameters are not integers or do not fit the
+/// bytes, or if the exit cannot be reached.
+pub fn run1(bytes: &Input, context: &Context) -> Answer {
+    let (size, fallen) = memory_space(bytes, context)?;
+    let mut grid = vec![vec![Tile::Free; size]; size];
+
+    for &(r, c) in bytes.iter().take(fallen) {
+        grid[r][c] = Tile::Corrupted;
+    }
+
+    let get_children = |x: Coords| {
+        let mut output = Vec::new();
+        for dir in [Direction::N, Direction::E, Direction::S, Direction::W] {
+            if let Some((r, c)) = dir.step_coords(x, size, size) {
+                if !matches!(grid[r][c], Tile::Corrupted) {
+                    output.push((r, c));
+                }
+            }
+        }
+
+        output
+    };
+
+    let is_tgt = |x: Coords| x == (size - 1, size - 1);
+
+    shortest_path_length((0, 0), is_tgt, get_children)
+        .map(|length| format!("{length}"))
+        .ok_or_else(|| SolveError::Failed("the exit cannot be reached".to_string()))
+}
+
+/// There is definitely a faster way to do this...
+///
+/// # Errors
+///
+/// Returns an error if the exit is never blocked off, or the parameters are
+/// not integers or do not fit the bytes.
+pub fn run2(bytes: &Input, context: &Context) -> Answer {
+    let (size, fallen) = memory_space(bytes, context)?;
+    let total = bytes.len();
+    let mut grid = vec![vec![Tile::Free; size]; size];
+
+    for &(r, c) in &bytes[..fallen] {
+        grid[r][c] = Tile::Corrupted;
+    }
+
+    let bytes = bytes.iter().copied().enumerate().skip(fallen);
+
+    let is_tgt = |x: Coords| x == (size - 1, size - 1);
+
+    for (i, (r, c)) in bytes {
+        grid[r][c] = Tile::Corrupted;
+        context.progress.update(i, total);
+
+        let get_children = |x: Coords| {
+            let mut output = Vec::new();
+            for dir in [Direction::N, Direction::E, Direction::S, Direction::W] {
+                if let Some((r, c)) = dir.step_coords(x, size, size) {
+                    if !matches!(grid[r][c], Tile::Corrupted) {
+                        output.push((r, c));
+                    }
+                }
+            }
+
+            output
+        };
+
+        if !exists_path((0, 0), is_tgt, get_children) {
+            return Ok(format!("{c},{r}"));
+        }
+    }
+
+    Err(SolveError::Failed("never blocked".to_string()))
+}
@@ -0,0 +1,101 @@
+use crate::{
+    graph::{count_paths, exists_path},
+    solution::Example,
+    utils::LinesIterator,
+};
+
+crate::solution!(2024, 19, "Linen Layout");
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb
+",
+    part1: Some("6"),
+    part2: Some("16"),
+    params: &[],
+}];
+
+/// The available towel patterns along with the desired designs.
+pub type Input = (Vec<String>, Vec<String>);
+
+/// # Panics
+pub fn parse(lines: &mut LinesIterator) -> Input {
+    let available = lines
+        .next()
+        .unwrap()
+        .unwrap()
+        .split(", ")
+        .map(std::string::ToString::to_string)
+        .collect();
+
+    lines.next();
+
+    let designs = lines.map(Result::unwrap).collect();
+
+    (available, designs)
+}
+
+#[must_use]
+pub fn run1((available, designs): &Input) -> String {
+    let mut possible = 0;
+
+    for design in designs {
+        let get_children = |cur: String| {
+            let mut output = Vec::new();
+
+            for a in available {
+                let cur = cur.clone();
+                let new = cur + a;
+
+                if design.starts_with(&new) {
+                    output.push(new);
+                }
+            }
+
+            output
+        };
+
+        let is_tgt = |cur: String| cur == *design;
+
+        if exists_path(String::new(), is_tgt, get_children) {
+            possible += 1;
+        }
+    }
+
+    format!("{possible}")
+}
+
+/// Outputs the number of ways of making a design out of the available
+/// patterns, counting the paths from its start to its end through the
+/// positions where one pattern can end and the next begin.
+fn count_ways(design: &str, available: &[String]) -> u128 {
+    let get_children = |i: usize| {
+        available
+            .iter()
+            .filter(|a| design[i..].starts_with(a.as_str()))
+            .map(|a| i + a.len())
+            .collect()
+    };
+
+    count_paths(0, &|i| i == design.len(), &get_children)
+        .expect("patterns are never empty, so positions only increase")
+}
+
+#[must_use]
+pub fn run2((available, designs): &Input) -> String {
+    let result: u128 = designs
+        .iter()
+        .map(|design| count_ways(design, available))
+        .sum();
+
+    format!("{result}")
+}
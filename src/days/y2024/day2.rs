@@ -1,28 +1,47 @@
+use crate::solution::Example;
 use crate::utils::LinesIterator;
 
-pub fn run1(lines: &mut LinesIterator) -> String {
-    let num_safe = prepare_lines(lines)
-        .filter(|line| check_safe_1(line))
-        .count();
-    format!("{num_safe}")
+crate::solution!(2024, 2, "Red-Nosed Reports");
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+",
+    part1: Some("2"),
+    part2: Some("4"),
+    params: &[],
+}];
+
+/// The levels of each report.
+pub type Input = Vec<Vec<i32>>;
+
+/// # Panics
+pub fn parse(lines: &mut LinesIterator) -> Input {
+    lines
+        .map(Result::unwrap)
+        .map(|x| {
+            x.split_whitespace()
+                .map(|x| x.parse::<i32>().unwrap())
+                .collect::<Vec<_>>()
+        })
+        .collect()
 }
 
-pub fn run2(lines: &mut LinesIterator) -> String {
-    let num_safe = prepare_lines(lines)
-        .filter(|line| check_safe_2_naive(line))
-        .count();
+#[must_use]
+pub fn run1(input: &Input) -> String {
+    let num_safe = input.iter().filter(|line| check_safe_1(line)).count();
     format!("{num_safe}")
 }
 
-// Lifetime annotation here is not actually needless, so we need to tell clippy to stop
-// complaining.
-#[allow(clippy::needless_lifetimes)]
-fn prepare_lines<'a>(lines: &'a mut LinesIterator) -> impl Iterator<Item = Vec<i32>> + 'a {
-    lines.map(Result::unwrap).map(|x| {
-        x.split_whitespace()
-            .map(|x| x.parse::<i32>().unwrap())
-            .collect::<Vec<_>>()
-    })
+#[must_use]
+pub fn run2(input: &Input) -> String {
+    let num_safe = input.iter().filter(|line| check_safe_2_naive(line)).count();
+    format!("{num_safe}")
 }
 
 fn check_safe_1(line: &[i32]) -> bool {
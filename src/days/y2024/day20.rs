@@ -1,12 +1,50 @@
 use crate::{
     direction::{Coords, Direction},
     graph::{get_dist, shortest_path},
+    solution::{Answer, Context, Example, Param, SolveError},
     utils::LinesIterator,
 };
 use std::collections::{HashSet, VecDeque};
 
+crate::solution!(2024, 20, "Race Condition", params);
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############",
+    part1: Some("1"),
+    part2: Some("285"),
+    params: &[("threshold", "50")],
+}];
+
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "threshold",
+        default: "100",
+        description: "fewest picoseconds a cheat must save to be counted",
+    },
+    Param {
+        name: "max_steps",
+        default: "20",
+        description: "most picoseconds a cheat may last in part 2",
+    },
+];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Tile {
+pub enum Tile {
     Wall,
     Empty,
 }
@@ -20,7 +58,10 @@ impl Tile {
     }
 }
 
-fn parse_input(lines: &mut LinesIterator) -> (Vec<Vec<Tile>>, Coords, Coords) {
+/// The racetrack along with its start and end.
+pub type Input = (Vec<Vec<Tile>>, Coords, Coords);
+
+pub fn parse(lines: &mut LinesIterator) -> Input {
     let mut grid = Vec::new();
     let mut start: Coords = (0, 0);
     let mut end: Coords = (0, 0);
@@ -44,10 +85,14 @@ fn parse_input(lines: &mut LinesIterator) -> (Vec<Vec<Tile>>, Coords, Coords) {
     (grid, start, end)
 }
 
-pub fn run1(lines: &mut LinesIterator) -> String {
-    let (grid, src, tgt) = parse_input(lines);
+/// # Errors
+///
+/// Returns an error if the threshold is not an integer, or the end cannot be
+/// reached.
+pub fn run1(input: &Input, context: &Context) -> Answer {
+    let &(ref grid, src, tgt) = input;
 
-    let threshold = 100;
+    let threshold: usize = context.param("threshold")?;
     let height = grid.len();
     let width = grid[0].len();
 
@@ -75,7 +120,8 @@ pub fn run1(lines: &mut LinesIterator) -> String {
         output
     };
 
-    let path = shortest_path(src, tgt, get_empty_nbrs).unwrap();
+    let path = shortest_path(src, tgt, get_empty_nbrs)
+        .ok_or_else(|| SolveError::Failed("the end cannot be reached".to_string()))?;
 
     let dist = get_dist(src, get_empty_nbrs);
 
@@ -93,14 +139,18 @@ pub fn run1(lines: &mut LinesIterator) -> String {
         }
     }
 
-    format!("{output}")
+    Ok(format!("{output}"))
 }
 
-pub fn run2(lines: &mut LinesIterator) -> String {
-    let (grid, src, tgt) = parse_input(lines);
+/// # Errors
+///
+/// Returns an error if the threshold or the length of cheats is not an
+/// integer, or the end cannot be reached.
+pub fn run2(input: &Input, context: &Context) -> Answer {
+    let &(ref grid, src, tgt) = input;
 
-    let threshold: usize = 100;
-    let max_steps: usize = 20;
+    let threshold: usize = context.param("threshold")?;
+    let max_steps: usize = context.param("max_steps")?;
     let height = grid.len();
     let width = grid[0].len();
 
@@ -128,7 +178,8 @@ pub fn run2(lines: &mut LinesIterator) -> String {
         output
     };
 
-    let path = shortest_path(src, tgt, get_empty_nbrs).unwrap();
+    let path = shortest_path(src, tgt, get_empty_nbrs)
+        .ok_or_else(|| SolveError::Failed("the end cannot be reached".to_string()))?;
 
     let dist = get_dist(src, get_empty_nbrs);
 
@@ -136,13 +187,13 @@ pub fn run2(lines: &mut LinesIterator) -> String {
 
     let mut output = 0;
 
-    for cheat_start in path {
+    let total = path.len();
+    for (i, cheat_start) in path.into_iter().enumerate() {
+        context.progress.update(i, total);
         let mut seen = HashSet::new();
         let mut to_check = VecDeque::new();
         to_check.push_back((cheat_start, 0));
-        while !to_check.is_empty() {
-            let (u, d) = to_check.pop_front().unwrap();
-
+        while let Some((u, d)) = to_check.pop_front() {
             for wall_nbr in get_wall_nbrs(u) {
                 if d < max_steps && seen.insert(wall_nbr) {
                     to_check.push_back((wall_nbr, d + 1));
@@ -160,5 +211,5 @@ pub fn run2(lines: &mut LinesIterator) -> String {
         }
     }
 
-    format!("{output}")
+    Ok(format!("{output}"))
 }
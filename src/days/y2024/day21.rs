@@ -1,7 +1,22 @@
-use crate::{graph::shortest_path_length, utils::LinesIterator};
+use crate::{graph::shortest_path_length, solution::Example, utils::LinesIterator};
+
+crate::solution!(2024, 21, "Keypad Conundrum", part2 unimplemented);
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+029A
+980A
+179A
+456A
+379A
+",
+    part1: Some("126384"),
+    part2: None,
+    params: &[],
+}];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum NumKey {
+pub enum NumKey {
     A,
     N0,
     N1,
@@ -321,12 +336,19 @@ fn shortest_input_sequence(src: NumKey, tgt: NumKey, keypads: usize) -> Vec<DirK
     options.pop().unwrap()
 }
 
-pub fn run1(lines: &mut LinesIterator) -> String {
-    let codes: Vec<Vec<_>> = lines
+/// The keys of each code to type on the numeric keypad.
+pub type Input = Vec<Vec<NumKey>>;
+
+pub fn parse(lines: &mut LinesIterator) -> Input {
+    lines
         .map(Result::unwrap)
         .map(|s| s.chars().map(NumKey::from).collect())
-        .collect();
+        .collect()
+}
 
+/// # Panics
+#[must_use]
+pub fn run1(codes: &Input) -> String {
     let mut output = 0;
 
     for code in codes {
@@ -340,7 +362,7 @@ pub fn run1(lines: &mut LinesIterator) -> String {
         let mut to_add = 0;
 
         let mut cur = NumKey::A;
-        for key in code {
+        for &key in code {
             to_add += shortest_input_sequence(cur, key, 3).len();
             cur = key;
         }
@@ -350,8 +372,3 @@ pub fn run1(lines: &mut LinesIterator) -> String {
 
     format!("{output}")
 }
-
-pub fn run2(lines: &mut LinesIterator) -> String {
-    lines.next();
-    format!("{lines:?}")
-}
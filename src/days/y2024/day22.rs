@@ -0,0 +1 @@
+crate::solution!(2024, 22, "Monkey Market", unimplemented);
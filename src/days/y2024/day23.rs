@@ -0,0 +1 @@
+crate::solution!(2024, 23, "LAN Party", unimplemented);
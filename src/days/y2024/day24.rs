@@ -0,0 +1 @@
+crate::solution!(2024, 24, "Crossed Wires", unimplemented);
@@ -0,0 +1 @@
+crate::solution!(2024, 25, "Code Chronicle", unimplemented);
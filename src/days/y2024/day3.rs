@@ -1,22 +1,54 @@
+use crate::solution::Example;
 use crate::utils::LinesIterator;
 
 use regex::Regex;
 
+crate::solution!(2024, 3, "Mull It Over");
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: "\
+xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))
+",
+        part1: Some("161"),
+        part2: None,
+        params: &[],
+    },
+    Example {
+        input: "\
+xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))
+",
+        part1: None,
+        part2: Some("48"),
+        params: &[],
+    },
+];
+
+/// The lines of corrupted memory, which instructions may not span.
+pub type Input = Vec<String>;
+
+/// # Panics
+pub fn parse(lines: &mut LinesIterator) -> Input {
+    lines.map(Result::unwrap).collect()
+}
+
 /// # Panics
-pub fn run1(lines: &mut LinesIterator) -> String {
+#[must_use]
+pub fn run1(input: &Input) -> String {
     let mut result = 0;
-    for line in lines.map(Result::unwrap) {
-        result += evaluate_1(&line).unwrap();
+    for line in input {
+        result += evaluate_1(line).unwrap();
     }
     format!("{result}")
 }
 
 /// # Panics
-pub fn run2(lines: &mut LinesIterator) -> String {
+#[must_use]
+pub fn run2(input: &Input) -> String {
     let mut result = 0;
     let mut do_multiply = true;
-    for line in lines.map(Result::unwrap) {
-        result += evaluate_2(&line, &mut do_multiply).unwrap();
+    for line in input {
+        result += evaluate_2(line, &mut do_multiply).unwrap();
     }
     format!("{result}")
 }
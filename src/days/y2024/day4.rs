@@ -1,13 +1,41 @@
-use crate::utils::{LinesIterator, lines_to_grid_of_chars};
+use crate::solution::Example;
+use crate::utils::{lines_to_grid_of_chars, LinesIterator};
+
+crate::solution!(2024, 4, "Ceres Search");
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+",
+    part1: Some("18"),
+    part2: Some("9"),
+    params: &[],
+}];
+
+/// The word search.
+pub type Input = Vec<Vec<char>>;
+
+pub fn parse(lines: &mut LinesIterator) -> Input {
+    lines_to_grid_of_chars(lines).collect()
+}
 
-pub fn run1(lines: &mut LinesIterator) -> String {
-    let grid: Vec<Vec<char>> = lines_to_grid_of_chars(lines).collect();
+#[must_use]
+pub fn run1(grid: &Input) -> String {
     let mut output: u32 = 0;
     for i in 0..grid.len() {
         for j in 0..grid[i].len() {
             let c = grid[i][j];
             if c == 'X' {
-                output += u32::from(check_xmas(&grid, i, j));
+                output += u32::from(check_xmas(grid, i, j));
             }
         }
     }
@@ -15,13 +43,13 @@ pub fn run1(lines: &mut LinesIterator) -> String {
     format!("{output}")
 }
 
-pub fn run2(lines: &mut LinesIterator) -> String {
-    let grid: Vec<Vec<char>> = lines_to_grid_of_chars(lines).collect();
+#[must_use]
+pub fn run2(grid: &Input) -> String {
     let mut output: u32 = 0;
     for i in 0..grid.len() {
         for j in 0..grid[i].len() {
             let c = grid[i][j];
-            if c == 'A' && check_x(&grid, i, j) {
+            if c == 'A' && check_x(grid, i, j) {
                 output += 1;
             }
         }
@@ -56,11 +84,12 @@ fn check_x(grid: &[Vec<char>], r: usize, c: usize) -> bool {
     if valid.contains(&my_chars) {
         true
     } else {
-        //println!("{my_chars:?}");
-        //println!("{:?}", &grid[r - 1][c - 1..=c + 1]);
-        //println!("{:?}", &grid[r][c - 1..=c + 1]);
-        //println!("{:?}", &grid[r + 1][c - 1..=c + 1]);
-        //println!();
+        crate::trace!(
+            "no X-MAS centred at ({r}, {c}): {my_chars:?} in {:?} {:?} {:?}",
+            &grid[r - 1][c - 1..=c + 1],
+            &grid[r][c - 1..=c + 1],
+            &grid[r + 1][c - 1..=c + 1]
+        );
         false
     }
 }
@@ -0,0 +1,123 @@
+use crate::graph::topo_sort;
+use crate::solution::{Answer, Example, SolveError};
+use crate::utils::LinesIterator;
+use std::collections::HashMap;
+
+crate::solution!(2024, 5, "Print Queue");
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+",
+    part1: Some("143"),
+    part2: Some("123"),
+    params: &[],
+}];
+
+pub struct Input {
+    /// Page ordering rules: the pages which must come after each page.
+    rules: HashMap<usize, Vec<usize>>,
+    updates: Vec<Vec<usize>>,
+}
+
+/// # Panics
+pub fn parse(lines: &mut LinesIterator) -> Input {
+    let mut rules: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut updates: Vec<Vec<usize>> = vec![];
+
+    while let Some(Ok(line)) = lines.next() {
+        if line.contains('|') {
+            let v: Vec<&str> = line.split('|').collect();
+            rules
+                .entry(v[0].parse().unwrap())
+                .or_default()
+                .push(v[1].parse().unwrap());
+        } else if line.contains(',') {
+            let v: Vec<usize> = line
+                .split(',')
+                .map(|x| x.parse::<usize>().unwrap())
+                .collect();
+            updates.push(v);
+        }
+    }
+
+    Input { rules, updates }
+}
+
+/// Outputs the pages of an update in the order given by the rules that apply
+/// to them, which is the order they are already in if it is correct.
+fn sort_update(
+    update: &[usize],
+    rules: &HashMap<usize, Vec<usize>>,
+) -> Result<Vec<usize>, SolveError> {
+    let get_children = |page| {
+        rules.get(&page).map_or_else(Vec::new, |after| {
+            after
+                .iter()
+                .copied()
+                .filter(|page| update.contains(page))
+                .collect()
+        })
+    };
+
+    topo_sort(update, get_children).map_err(|cycle| {
+        SolveError::Failed(format!(
+            "the rules for update {update:?} go round in a cycle through {:?}",
+            cycle.0
+        ))
+    })
+}
+
+/// # Errors
+///
+/// Returns an error if the rules for some update contradict each other.
+pub fn run1(input: &Input) -> Answer {
+    let mut output = 0;
+    for update in &input.updates {
+        if sort_update(update, &input.rules)? == *update {
+            output += update[update.len() / 2];
+        }
+    }
+    Ok(format!("{output}"))
+}
+
+/// # Errors
+///
+/// Returns an error if the rules for some update contradict each other.
+pub fn run2(input: &Input) -> Answer {
+    let mut output = 0;
+    for update in &input.updates {
+        let sorted = sort_update(update, &input.rules)?;
+        if sorted != *update {
+            output += sorted[sorted.len() / 2];
+        }
+    }
+    Ok(format!("{output}"))
+}
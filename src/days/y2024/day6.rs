@@ -1,8 +1,27 @@
+use crate::solution::{Context, Example};
 use crate::utils::{lines_to_grid_of_chars, LinesIterator};
-use ::std::time::{Duration, Instant};
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
-use std::io::Write;
+
+crate::solution!(2024, 6, "Guard Gallivant");
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+",
+    part1: Some("41"),
+    part2: Some("6"),
+    params: &[],
+}];
 
 // tuple of either (-1,0), (0,1), (1,0), or (0,-1).
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
@@ -91,6 +110,22 @@ impl Board {
         self.check_for_loops = true;
     }
 
+    /// Draws the board with every position the guard visited marked `X`.
+    fn render(&self) -> String {
+        let mut output = String::new();
+        for (r, line) in self.grid.iter().enumerate() {
+            for (c, &character) in line.iter().enumerate() {
+                if self.guard_visited.contains_key(&(r, c)) {
+                    output.push('X');
+                } else {
+                    output.push(character);
+                }
+            }
+            output.push('\n');
+        }
+        output
+    }
+
     fn step(&mut self) -> StepOutput {
         let height = self.grid.len();
         assert!(height > 0);
@@ -162,33 +197,36 @@ impl Board {
     }
 }
 
-pub fn run1(lines: &mut LinesIterator) -> String {
-    let grid: Vec<Vec<char>> = lines_to_grid_of_chars(lines).collect();
-    let mut board = Board::new(grid);
+pub type Input = Vec<Vec<char>>;
+
+pub fn parse(lines: &mut LinesIterator) -> Input {
+    lines_to_grid_of_chars(lines).collect()
+}
+
+#[must_use]
+pub fn run1(grid: &Input) -> String {
+    let mut board = Board::new(grid.clone());
 
     while !board.guard_off_map {
         let result = board.step();
-        //println!(
-        //    "{:?}",
-        //    board
-        //        .clone()
-        //        .guard_visited
-        //        .keys()
-        //        .collect::<Vec<&(usize, usize)>>()
-        //);
-        //println!();
+        crate::trace!(
+            "guard at ({}, {}) after {result:?}, {} positions visited",
+            board.guard_r,
+            board.guard_c,
+            board.guard_visited.len()
+        );
         if matches!(result, StepOutput::Loop) {
-            println!("loop!");
             break;
         }
     }
+    crate::debug!("path of the guard:\n{}", board.render());
 
     format!("{}", board.guard_visited.keys().count())
 }
 
 /// # Panics
-pub fn run2(lines: &mut LinesIterator) -> String {
-    let grid: Vec<_> = lines_to_grid_of_chars(lines).collect();
+#[must_use]
+pub fn run2(grid: &Input, context: &Context) -> String {
     let mut num_loops = 0;
     let mut board = Board::new(grid.clone());
     let r0 = board.guard_r;
@@ -196,7 +234,6 @@ pub fn run2(lines: &mut LinesIterator) -> String {
 
     while !board.guard_off_map {
         if matches!(board.step(), StepOutput::Loop) {
-            println!("loop");
             break;
         }
     }
@@ -207,12 +244,10 @@ pub fn run2(lines: &mut LinesIterator) -> String {
         .filter(|&&x| x != (r0, c0))
         .copied();
 
-    let mut b = Board::new(grid);
+    let mut b = Board::new(grid.clone());
 
     let num_to_check = to_visit.clone().count();
 
-    let mut now = Instant::now();
-
     for (i, (r, c)) in to_visit.enumerate() {
         b.grid[r][c] = '#';
 
@@ -226,26 +261,8 @@ pub fn run2(lines: &mut LinesIterator) -> String {
         b.grid[r][c] = '.';
         b.reset(r0, c0);
 
-        if now.elapsed() >= Duration::from_secs_f32(0.1) {
-            let percentage = 100.0 * (i as f64) / (num_to_check as f64);
-            print!("\rProgress: {percentage:.2}%");
-            std::io::stdout().flush().unwrap();
-            now = Instant::now();
-        }
+        context.progress.update(i + 1, num_to_check);
     }
-    print!("\rProgress: 100.00%");
-    println!();
 
     format!("{num_loops}")
 }
-
-#[allow(dead_code)]
-fn print_board(b: &Vec<Vec<char>>) {
-    for line in b {
-        for c in line {
-            print!("{c}");
-        }
-        println!();
-    }
-    println!();
-}
@@ -1,8 +1,31 @@
+use crate::solution::{Context, Example};
 use crate::utils::LinesIterator;
 
+crate::solution!(2024, 7, "Bridge Repair");
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+",
+    part1: Some("3749"),
+    part2: Some("11387"),
+    params: &[],
+}];
+
+/// Each equation's test value along with its numbers.
+pub type Input = Vec<(u64, Vec<u64>)>;
+
 /// # Panics
-pub fn run1(lines: &mut LinesIterator) -> String {
-    let data = lines
+pub fn parse(lines: &mut LinesIterator) -> Input {
+    lines
         .map(Result::unwrap)
         .map(|x| x.split(": ").map(String::from).collect::<Vec<String>>())
         .map(|x| {
@@ -12,12 +35,16 @@ pub fn run1(lines: &mut LinesIterator) -> String {
                     .map(|x| x.parse::<u64>().unwrap())
                     .collect::<Vec<u64>>(),
             )
-        });
+        })
+        .collect()
+}
 
+#[must_use]
+pub fn run1(data: &Input) -> String {
     let mut output = 0;
 
-    for (target, v) in data {
-        if test(target, &v).is_ok() {
+    for &(target, ref v) in data {
+        if test(target, v).is_ok() {
             output += target;
         }
     }
@@ -94,26 +121,15 @@ fn test_2(target: u64, v: &[u64]) -> Result<u64, TestError> {
         .ok_or(TestError::TargetNotFound)
 }
 
-/// # Panics
-pub fn run2(lines: &mut LinesIterator) -> String {
-    let data = lines
-        .map(Result::unwrap)
-        .map(|x| x.split(": ").map(String::from).collect::<Vec<String>>())
-        .map(|x| {
-            (
-                x[0].parse::<u64>().unwrap(),
-                x[1].split(' ')
-                    .map(|x| x.parse::<u64>().unwrap())
-                    .collect::<Vec<u64>>(),
-            )
-        });
-
+#[must_use]
+pub fn run2(data: &Input, context: &Context) -> String {
     let mut output = 0;
 
-    for (target, v) in data {
-        if test_2(target, &v).is_ok() {
+    for (i, &(target, ref v)) in data.iter().enumerate() {
+        if test_2(target, v).is_ok() {
             output += target;
         }
+        context.progress.update(i + 1, data.len());
     }
 
     format!("{output}")
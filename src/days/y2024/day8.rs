@@ -1,8 +1,31 @@
+use crate::solution::Example;
 use crate::utils::{lines_to_grid_of_chars, LinesIterator};
 use num::CheckedSub;
 use std::collections::{HashMap, HashSet};
 use std::ops::{Add, Sub};
 
+crate::solution!(2024, 8, "Resonant Collinearity");
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............
+",
+    part1: Some("14"),
+    part2: Some("34"),
+    params: &[],
+}];
+
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
 struct P<T>(T, T);
 
@@ -36,7 +59,14 @@ where
     }
 }
 
-pub fn run1(lines: &mut LinesIterator) -> String {
+pub struct Input {
+    height: usize,
+    width: usize,
+    /// Positions of the antennae of each frequency.
+    map: HashMap<char, Vec<P<usize>>>,
+}
+
+pub fn parse(lines: &mut LinesIterator) -> Input {
     let grid: Vec<_> = lines_to_grid_of_chars(lines).collect();
     let height = grid.len();
     let width = grid[0].len();
@@ -51,6 +81,16 @@ pub fn run1(lines: &mut LinesIterator) -> String {
         }
     }
 
+    Input { height, width, map }
+}
+
+#[must_use]
+pub fn run1(input: &Input) -> String {
+    let &Input {
+        height,
+        width,
+        ref map,
+    } = input;
     let mut antinodes: HashSet<P<usize>> = HashSet::new();
 
     for antennae in map.values() {
@@ -91,6 +131,22 @@ fn get_antinodes(pos1: &P<usize>, pos2: &P<usize>, height: usize, width: usize)
     output
 }
 
+/// Draws the map with every antinode marked `#`.
+fn render(antinodes: &HashSet<P<usize>>, height: usize, width: usize) -> String {
+    let mut output = String::new();
+    for r in 0..height {
+        for c in 0..width {
+            output.push(if antinodes.contains(&P(r, c)) {
+                '#'
+            } else {
+                '.'
+            });
+        }
+        output.push('\n');
+    }
+    output
+}
+
 fn get_antinodes_2(pos1: &P<usize>, pos2: &P<usize>, height: usize, width: usize) -> Vec<P<usize>> {
     let mut output = vec![];
 
@@ -121,21 +177,13 @@ fn get_antinodes_2(pos1: &P<usize>, pos2: &P<usize>, height: usize, width: usize
     output
 }
 
-pub fn run2(lines: &mut LinesIterator) -> String {
-    let grid: Vec<_> = lines_to_grid_of_chars(lines).collect();
-    let height = grid.len();
-    let width = grid[0].len();
-
-    let mut map: HashMap<char, Vec<P<usize>>> = HashMap::new();
-
-    for (i, line) in grid.iter().enumerate() {
-        for (j, c) in line.iter().enumerate() {
-            if *c != '.' {
-                map.entry(*c).or_default().push(P(i, j));
-            }
-        }
-    }
-
+#[must_use]
+pub fn run2(input: &Input) -> String {
+    let &Input {
+        height,
+        width,
+        ref map,
+    } = input;
     let mut antinodes: HashSet<P<usize>> = HashSet::new();
 
     for antennae in map.values() {
@@ -151,22 +199,7 @@ pub fn run2(lines: &mut LinesIterator) -> String {
         }
     }
 
-    // let mut output = 0;
-    //
-    // for p in antinodes {
-    //     grid[p.0][p.1] = '#';
-    //     output += 1;
-    // }
-
-    // for line in grid {
-    //     for c in line {
-    //         print!("{c}");
-    //     }
-    //     println!();
-    // }
-    // println!();
-
-    // format!("{output}")
+    crate::debug!("antinodes:\n{}", render(&antinodes, height, width));
 
     format!("{}", antinodes.len())
 }
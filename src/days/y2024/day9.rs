@@ -1,11 +1,23 @@
+use crate::solution::Example;
 use crate::utils::LinesIterator;
 use rand::Rng;
 use std::cmp::Ordering;
 use std::collections::VecDeque;
 
+crate::solution!(2024, 9, "Disk Fragmenter");
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+2333133121414131402
+",
+    part1: Some("1928"),
+    part2: Some("2858"),
+    params: &[],
+}];
+
 // I was crossed and sleep deprived when I wrote this function no idea how it works tbh
-fn parse(line: &[u32]) -> usize {
-    // print_vec_num(line);
+fn checksum(line: &[u32]) -> usize {
+    crate::trace!("disk map: {line:?}");
     let length = line.len();
     let mut to_borrow: VecDeque<(usize, u32)> = VecDeque::new();
     let mut j = if length % 2 == 1 { length + 1 } else { length };
@@ -13,22 +25,18 @@ fn parse(line: &[u32]) -> usize {
     let mut result = 0;
     assert!(j % 2 == 0);
 
-    // let mut compressed_row = Vec::new();
     'outer: for (i, num) in line.iter().enumerate() {
-        // println!("----");
         let num = *num;
         if i % 2 == 0 {
             if i >= j {
                 assert!(to_borrow.len() == 1);
                 for _ in 0..to_borrow[0].1 {
-                    // compressed_row.push(to_borrow[0].0);
                     result += to_borrow[0].0 * k;
                     k += 1;
                 }
                 break;
             }
             for _ in 0..num {
-                // compressed_row.push(i / 2);
                 result += (i / 2) * k;
                 k += 1;
             }
@@ -45,20 +53,14 @@ fn parse(line: &[u32]) -> usize {
                     }
                 }
                 let id = to_borrow[0].0;
-                // compressed_row.push(id);
                 to_borrow[0].1 -= 1;
                 result += id * k;
                 k += 1;
             }
         }
-        // print_vec_num(&compressed_row);
-        // print_vecdq_num(&to_borrow);
-        // println!("i:{i}");
-        // println!("j:{j}");
+        crate::trace!("i: {i}, j: {j}, {k} blocks placed, borrowing {to_borrow:?}");
     }
 
-    // print_vec_num(&compressed_row);
-
     result
 }
 
@@ -69,7 +71,7 @@ fn move_back(row: &mut Vec<(Option<usize>, u32)>, ent: (usize, u32)) -> &Vec<(Op
         let (id_opt, num) = row[i];
         if let Some(id) = id_opt {
             if id == tgt_id {
-                // println!("did not move");
+                crate::trace!("file {tgt_id} did not move");
                 return row;
             }
         } else {
@@ -77,14 +79,14 @@ fn move_back(row: &mut Vec<(Option<usize>, u32)>, ent: (usize, u32)) -> &Vec<(Op
             match num.cmp(&tgt_num) {
                 Ordering::Less => continue,
                 Ordering::Greater => {
-                    // println!("inserted");
+                    crate::trace!("file {tgt_id} moved into a larger gap at {i}");
                     row[i].1 -= tgt_num;
                     row.insert(i, (Some(tgt_id), tgt_num));
                     last = i + 2;
                     break;
                 }
                 Ordering::Equal => {
-                    // println!("replaced");
+                    crate::trace!("file {tgt_id} moved into a gap of its size at {i}");
                     row[i] = (Some(tgt_id), tgt_num);
                     last = i + 1;
                     break;
@@ -106,20 +108,18 @@ fn move_back(row: &mut Vec<(Option<usize>, u32)>, ent: (usize, u32)) -> &Vec<(Op
     row
 }
 
-#[allow(dead_code)]
-fn print_row(row: &[(Option<usize>, u32)]) {
-    for (x, y) in row {
-        let x = *x;
-        let y = *y;
-        match x {
-            None => print!("{}", ".".repeat(y as usize)),
-            Some(id) => print!("{}", id.to_string().repeat(y as usize)),
-        }
+/// Draws the blocks of a row, with each file's blocks showing its id and free
+/// blocks showing `.`.
+fn render_row(row: &[(Option<usize>, u32)]) -> String {
+    let mut output = String::new();
+    for &(id, num) in row {
+        let block = id.map_or_else(|| ".".to_string(), |id| id.to_string());
+        output.push_str(&block.repeat(num as usize));
     }
-    println!();
+    output
 }
 
-fn parse_2(line: &[u32]) -> u64 {
+fn checksum_2(line: &[u32]) -> u64 {
     let mut row: Vec<(Option<usize>, u32)> = Vec::new();
     let mut to_move: Vec<(usize, u32)> = Vec::new();
     for (i, num) in line.iter().enumerate() {
@@ -131,10 +131,10 @@ fn parse_2(line: &[u32]) -> u64 {
         }
     }
 
-    // print_row(&row);
+    crate::trace!("{}", render_row(&row));
     for ent in to_move.iter().rev() {
         move_back(&mut row, *ent);
-        // print_row(&row);
+        crate::trace!("{}", render_row(&row));
     }
 
     let mut i = 0;
@@ -150,46 +150,30 @@ fn parse_2(line: &[u32]) -> u64 {
     result
 }
 
-pub fn run1(lines: &mut LinesIterator) -> String {
-    let result = parse(
-        &lines
-            .next()
-            .unwrap()
-            .unwrap()
-            .chars()
-            .map(|x| x.to_digit(10).unwrap())
-            .collect::<Vec<u32>>(),
-    );
-
-    format!("{result}")
+/// The digits of the disk map.
+pub type Input = Vec<u32>;
+
+/// # Panics
+pub fn parse(lines: &mut LinesIterator) -> Input {
+    lines
+        .next()
+        .unwrap()
+        .unwrap()
+        .chars()
+        .map(|x| x.to_digit(10).unwrap())
+        .collect()
 }
 
-#[allow(dead_code)]
-fn print_vecdq_num(v: &VecDeque<(usize, u32)>) {
-    for n in v {
-        print!("{n:?}");
-    }
-    println!();
-}
+#[must_use]
+pub fn run1(disk_map: &Input) -> String {
+    let result = checksum(disk_map);
 
-#[allow(dead_code)]
-fn print_vec_num<T: std::fmt::Debug>(v: &[T]) {
-    for n in v {
-        print!("{n:?}");
-    }
-    println!();
+    format!("{result}")
 }
 
-pub fn run2(lines: &mut LinesIterator) -> String {
-    let result = parse_2(
-        &lines
-            .next()
-            .unwrap()
-            .unwrap()
-            .chars()
-            .map(|x| x.to_digit(10).unwrap())
-            .collect::<Vec<u32>>(),
-    );
+#[must_use]
+pub fn run2(disk_map: &Input) -> String {
+    let result = checksum_2(disk_map);
 
     format!("{result}")
 }
@@ -0,0 +1,337 @@
+use crate::solution::Key;
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// The site puzzle inputs are downloaded from.
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable holding the session token used to download inputs.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// Environment variable overriding the directory downloaded inputs are cached
+/// in.
+pub const CACHE_DIR_ENV: &str = "AOC_CACHE_DIR";
+
+/// Name of the file in the cache directory the session token is read from when
+/// it is not in the environment.
+pub const SESSION_FILE: &str = "session";
+
+/// Sent with every request, as the site asks of automated tools.
+const USER_AGENT: &str = "github.com/isaiahtx/Advent-of-Code-2024";
+
+/// Minimal HTTP client, so that the site can be replaced by a local server or
+/// a stub in tests.
+pub trait HttpClient {
+    /// Sends a GET request to `url` with the given session cookie, outputting
+    /// the body of the response.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, or the response does not have a
+    /// success status.
+    fn get(&self, url: &str, session: &str) -> Result<String, String>;
+
+    /// Sends a POST request of a URL-encoded form to `url` with the given
+    /// session cookie, outputting the body of the response.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, or the response does not have a
+    /// success status.
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<String, String>;
+}
+
+/// The client used outside of tests.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UreqClient;
+
+impl UreqClient {
+    fn request(method: &str, url: &str, session: &str) -> ureq::Request {
+        ureq::request(method, url)
+            .set("Cookie", &format!("session={session}"))
+            .set("User-Agent", USER_AGENT)
+    }
+
+    fn read_body(
+        url: &str,
+        response: Result<ureq::Response, ureq::Error>,
+    ) -> Result<String, String> {
+        let response = response.map_err(|err| match err {
+            ureq::Error::Status(code, response) => {
+                let body = response.into_string().unwrap_or_default();
+                format!("{url} responded with status {code}: {}", body.trim())
+            }
+            ureq::Error::Transport(err) => format!("could not reach {url}: {err}"),
+        })?;
+
+        let mut body = String::new();
+        response
+            .into_reader()
+            .read_to_string(&mut body)
+            .map_err(|err| format!("could not read the response from {url}: {err}"))?;
+        Ok(body)
+    }
+}
+
+impl HttpClient for UreqClient {
+    fn get(&self, url: &str, session: &str) -> Result<String, String> {
+        Self::read_body(url, Self::request("GET", url, session).call())
+    }
+
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<String, String> {
+        Self::read_body(url, Self::request("POST", url, session).send_form(form))
+    }
+}
+
+/// Outputs the per-user directory downloaded inputs are cached in: the value
+/// of `AOC_CACHE_DIR` if set, otherwise `aoc` in the platform's cache
+/// directory.
+#[must_use]
+pub fn cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os(CACHE_DIR_ENV) {
+        return Some(PathBuf::from(dir));
+    }
+
+    let base = if cfg!(windows) {
+        env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Caches"))
+    } else {
+        env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+    };
+
+    base.map(|base| base.join("aoc"))
+}
+
+/// Outputs the per-user cache directory, failing with a message naming the
+/// variable to set if there is none.
+///
+/// # Errors
+///
+/// Returns an error if there is no cache directory.
+pub fn require_cache_dir() -> Result<PathBuf, String> {
+    cache_dir()
+        .ok_or_else(|| format!("could not find a cache directory; please set {CACHE_DIR_ENV}"))
+}
+
+/// Outputs the session token in `AOC_SESSION`, or else in the file `session`
+/// in the cache directory, if either is set.
+#[must_use]
+pub fn session_from_env(cache_dir: &Path) -> Option<String> {
+    env::var(SESSION_ENV)
+        .ok()
+        .or_else(|| fs::read_to_string(cache_dir.join(SESSION_FILE)).ok())
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+}
+
+/// Outputs the error reported when a session token is needed but missing.
+#[must_use]
+pub fn missing_session(cache_dir: &Path) -> String {
+    format!(
+        "no session token; please set {SESSION_ENV} or write it to {}",
+        cache_dir.join(SESSION_FILE).display()
+    )
+}
+
+/// The outcome of a successful [`Fetcher::fetch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fetched {
+    /// Where the input is cached.
+    pub path: PathBuf,
+    /// Whether the input was downloaded, rather than already cached.
+    pub downloaded: bool,
+}
+
+/// Downloads inputs into a cache directory, at most once each.
+pub struct Fetcher<'a> {
+    pub client: &'a dyn HttpClient,
+    pub base_url: String,
+    pub cache_dir: PathBuf,
+    /// The session token, which is only needed when something is downloaded.
+    pub session: Option<String>,
+}
+
+impl<'a> Fetcher<'a> {
+    /// Outputs a fetcher downloading from the real site into the per-user
+    /// cache directory, using the session token in `AOC_SESSION`, or else in
+    /// the file `session` in the cache directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no cache directory.
+    pub fn from_env(client: &'a dyn HttpClient) -> Result<Self, String> {
+        let cache_dir = require_cache_dir()?;
+        let session = session_from_env(&cache_dir);
+
+        Ok(Self {
+            client,
+            base_url: BASE_URL.to_string(),
+            cache_dir,
+            session,
+        })
+    }
+
+    /// Outputs the path the input of a day is cached at in `cache_dir`.
+    #[must_use]
+    pub fn path_in(cache_dir: &Path, key: Key) -> PathBuf {
+        cache_dir.join(format!("{}/day{:02}.txt", key.year, key.day))
+    }
+
+    /// Outputs the path the input of a day is cached at.
+    #[must_use]
+    pub fn path(&self, key: Key) -> PathBuf {
+        Self::path_in(&self.cache_dir, key)
+    }
+
+    /// Makes sure the input of a day is cached, downloading it only if it is
+    /// not there already.
+    ///
+    /// Nothing is written unless the whole input was downloaded, so a failed
+    /// download never leaves a partial input behind.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not cached and there is no session
+    /// token, the download fails, or the input cannot be written.
+    pub fn fetch(&self, key: Key) -> Result<Fetched, String> {
+        let path = self.path(key);
+        if path.is_file() {
+            return Ok(Fetched {
+                path,
+                downloaded: false,
+            });
+        }
+
+        let session = self
+            .session
+            .as_deref()
+            .ok_or_else(|| missing_session(&self.cache_dir))?;
+
+        let url = format!(
+            "{}/{}/day/{}/input",
+            self.base_url.trim_end_matches('/'),
+            key.year,
+            key.day
+        );
+        let input = self.client.get(&url, session)?;
+
+        let dir = path.parent().unwrap_or(&self.cache_dir);
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("could not create {}: {err}", dir.display()))?;
+
+        let partial = path.with_extension("part");
+        fs::write(&partial, input)
+            .and_then(|()| fs::rename(&partial, &path))
+            .map_err(|err| format!("could not write {}: {err}", path.display()))?;
+
+        Ok(Fetched {
+            path,
+            downloaded: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Serves `responses` in turn to one request each on a local port,
+    /// outputting the base URL of the server and a channel receiving the
+    /// request line and cookie of every request.
+    fn mock_server(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request = Vec::new();
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 0 && line != "\r\n" {
+                    let trimmed = line.trim_end();
+                    if trimmed.starts_with("GET") || trimmed.starts_with("Cookie") {
+                        request.push(trimmed.to_string());
+                    }
+                    line.clear();
+                }
+                sender.send(request.join("\n")).unwrap();
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (url, receiver)
+    }
+
+    fn temp_cache(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch() {
+        let (base_url, requests) = mock_server(vec![(200, "1 2\n3 4\n")]);
+        let cache_dir = temp_cache("aoc_test_fetch");
+        let fetcher = Fetcher {
+            client: &UreqClient,
+            base_url,
+            cache_dir: cache_dir.clone(),
+            session: Some("abc".to_string()),
+        };
+
+        let outcome = fetcher.fetch(Key::new(2024, 5)).unwrap();
+        assert!(outcome.downloaded);
+        assert_eq!(outcome.path, cache_dir.join("2024/day05.txt"));
+        assert_eq!(fs::read_to_string(&outcome.path).unwrap(), "1 2\n3 4\n");
+        assert_eq!(
+            requests.recv().unwrap(),
+            "GET /2024/day/5/input HTTP/1.1\nCookie: session=abc"
+        );
+
+        // The server only answers once, so this must come from the cache.
+        let outcome = fetcher.fetch(Key::new(2024, 5)).unwrap();
+        assert!(!outcome.downloaded);
+
+        fs::remove_dir_all(cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let (base_url, _requests) = mock_server(vec![(404, "Not unlocked yet")]);
+        let cache_dir = temp_cache("aoc_test_fetch_errors");
+        let mut fetcher = Fetcher {
+            client: &UreqClient,
+            base_url,
+            cache_dir: cache_dir.clone(),
+            session: None,
+        };
+
+        let err = fetcher.fetch(Key::new(2024, 25)).unwrap_err();
+        assert!(err.contains(SESSION_ENV), "{err}");
+
+        fetcher.session = Some("abc".to_string());
+        let err = fetcher.fetch(Key::new(2024, 25)).unwrap_err();
+        assert!(err.contains("404"), "{err}");
+        assert!(!fetcher.path(Key::new(2024, 25)).exists());
+
+        let _ = fs::remove_dir_all(cache_dir);
+    }
+}
@@ -1,9 +1,48 @@
+use crate::direction::Coords;
+use num::rational::Ratio;
+use num::{CheckedAdd, CheckedMul, Integer, Zero};
 use std::cmp::Reverse;
 use std::collections::hash_map::Entry::Vacant;
 use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::fmt::Debug;
 use std::hash::Hash;
 
+/// The cost of an edge or a path in a **weighted** graph.
+///
+/// Implemented for the primitive integers and `num`'s ratios of them, whose
+/// additions check for overflow, as a wrapped cost would silently make a path
+/// look cheaper than it is. Other ordered types with a zero, including ones
+/// from other crates, can implement it too, checking for overflow or not.
+/// Costs are assumed not to be negative, as otherwise the paths found may not
+/// be the cheapest.
+pub trait Cost: Copy + Ord + Debug + Zero {
+    /// Outputs the sum of two costs, or `None` if it overflows, in which case
+    /// the path whose cost it would be is never taken.
+    fn checked_add_cost(self, other: Self) -> Option<Self>;
+}
+
+/// Implements [`Cost`] for primitive integers, with their checked addition.
+macro_rules! integer_costs {
+    ($($t:ty),* $(,)?) => {
+        $(impl Cost for $t {
+            fn checked_add_cost(self, other: Self) -> Option<Self> {
+                self.checked_add(other)
+            }
+        })*
+    };
+}
+
+integer_costs!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl<T> Cost for Ratio<T>
+where
+    T: Copy + Debug + Integer + CheckedAdd + CheckedMul,
+{
+    fn checked_add_cost(self, other: Self) -> Option<Self> {
+        CheckedAdd::checked_add(&self, &other)
+    }
+}
+
 /// Gets the number of targets that are reachable from the source in an
 /// **unweighted** graph.
 pub fn num_reachable_targets<T, F1, F2>(src: T, is_tgt: F1, get_children: F2) -> usize
@@ -83,25 +122,25 @@ where
 
 /// Outputs map containing the distance of any node from the source in a
 /// **weighted** graph, provided the node is reachable from the source.
-pub fn dijkstra<T, F>(src: T, get_children: &mut F) -> HashMap<T, usize>
+pub fn dijkstra<T, C, F>(src: T, get_children: &mut F) -> HashMap<T, C>
 where
     T: Eq + Hash + Copy + Debug + Ord,
-    F: FnMut(T) -> Vec<(usize, T)>,
+    C: Cost,
+    F: FnMut(T) -> Vec<(C, T)>,
 {
-    let mut pq: BinaryHeap<(Reverse<usize>, T)> = BinaryHeap::new();
-    let mut dist: HashMap<T, usize> = HashMap::new();
+    let mut pq: BinaryHeap<(Reverse<C>, T)> = BinaryHeap::new();
+    let mut dist: HashMap<T, C> = HashMap::new();
     let mut removed_from_pq: HashSet<T> = HashSet::new();
 
-    pq.push((Reverse(usize::MIN), src));
-    dist.insert(src, usize::MIN);
+    pq.push((Reverse(C::zero()), src));
+    dist.insert(src, C::zero());
 
     while !pq.is_empty() {
         if let Some((Reverse(distance), u)) = pq.pop() {
             removed_from_pq.insert(u);
             for (weight, nbr) in get_children(u) {
                 if removed_from_pq.contains(&nbr) {
-                } else {
-                    let alt = distance + weight;
+                } else if let Some(alt) = distance.checked_add_cost(weight) {
                     if !dist.contains_key(&nbr) || alt < dist[&nbr] {
                         dist.insert(nbr, alt);
                         pq.push((Reverse(alt), nbr));
@@ -340,27 +379,28 @@ where
 }
 
 /// Outputs a shortest path from a source to a target in a **weighted** graph.
-pub fn shortest_path_weighted<T, F1, F2>(
+pub fn shortest_path_weighted<T, C, F1, F2>(
     src: T,
     is_tgt: &F1,
     get_children: &mut F2,
-) -> Option<Vec<(T, usize)>>
+) -> Option<Vec<(T, C)>>
 where
     T: Eq + Hash + Copy + Debug + Ord,
+    C: Cost,
     F1: Fn(T) -> bool,
-    F2: FnMut(T) -> Vec<(usize, T)>,
+    F2: FnMut(T) -> Vec<(C, T)>,
 {
     if is_tgt(src) {
-        return Some(Vec::from([(src, 0)]));
+        return Some(Vec::from([(src, C::zero())]));
     }
 
-    let mut pq: BinaryHeap<(Reverse<usize>, T)> = BinaryHeap::new();
-    let mut dist: HashMap<T, usize> = HashMap::new();
+    let mut pq: BinaryHeap<(Reverse<C>, T)> = BinaryHeap::new();
+    let mut dist: HashMap<T, C> = HashMap::new();
     let mut prev: HashMap<T, Option<T>> = HashMap::new();
     let mut removed_from_pq: HashSet<T> = HashSet::new();
 
-    pq.push((Reverse(usize::MIN), src));
-    dist.insert(src, usize::MIN);
+    pq.push((Reverse(C::zero()), src));
+    dist.insert(src, C::zero());
     prev.insert(src, None);
 
     while !pq.is_empty() {
@@ -380,8 +420,7 @@ where
 
             for (weight, nbr) in get_children(u) {
                 if removed_from_pq.contains(&nbr) {
-                } else {
-                    let alt = distance + weight;
+                } else if let Some(alt) = distance.checked_add_cost(weight) {
                     if !dist.contains_key(&nbr) || alt < dist[&nbr] {
                         dist.insert(nbr, alt);
                         prev.insert(nbr, Some(u));
@@ -395,45 +434,151 @@ where
     None
 }
 
-/// Outputs the number of paths from source to a target in an **unweighted**
-/// graph, where we are assuming the source is not a target.
-pub fn num_paths<T, F1, F2>(src: T, is_tgt: &F1, get_children: &F2) -> usize
+/// A cycle found in a graph that should have none: each node has an edge to
+/// the next, and the last node has one to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<T>(pub Vec<T>);
+
+/// Counts the paths from a source to the targets of an **unweighted** graph
+/// with a depth-first search, where the paths from each node are only counted
+/// once, however many paths lead to it. `at_tgt` outputs the count of paths of
+/// a target, and `add` adds one count to another.
+fn count_paths_with<T, C, F1, F2, F3, F4>(
+    src: T,
+    is_tgt: &F1,
+    get_children: &F2,
+    at_tgt: F3,
+    add: F4,
+) -> Result<C, Cycle<T>>
 where
-    T: Eq + Hash + Debug + Copy,
+    T: Eq + Hash + Copy + Debug,
+    C: Default + Clone,
     F1: Fn(T) -> bool,
     F2: Fn(T) -> Vec<T>,
+    F3: Fn(T) -> C,
+    F4: Fn(&mut C, &C),
 {
-    // Assuming src != tgt
-    // assert_ne!(src, tgt);
-    let mut count = 0;
-    for nbr in get_children(src) {
-        if is_tgt(nbr) {
-            count += 1;
+    if is_tgt(src) {
+        return Ok(at_tgt(src));
+    }
+
+    let mut counts: HashMap<T, C> = HashMap::new();
+
+    // The path currently being explored, each node along with its children
+    // left to explore and the number of paths found from it so far.
+    let mut stack: Vec<(T, Vec<T>, C)> = vec![(src, get_children(src), C::default())];
+    let mut on_stack: HashSet<T> = HashSet::from([src]);
+
+    while let Some((_, children, _)) = stack.last_mut() {
+        if let Some(child) = children.pop() {
+            if let Some(count) = counts.get(&child) {
+                add(&mut stack.last_mut().unwrap().2, count);
+            } else if on_stack.contains(&child) {
+                let cycle = stack
+                    .iter()
+                    .map(|(node, _, _)| *node)
+                    .skip_while(|&node| node != child)
+                    .collect();
+                return Err(Cycle(cycle));
+            } else if is_tgt(child) {
+                let count = at_tgt(child);
+                add(&mut stack.last_mut().unwrap().2, &count);
+                counts.insert(child, count);
+            } else {
+                on_stack.insert(child);
+                stack.push((child, get_children(child), C::default()));
+            }
         } else {
-            count += num_paths(nbr, is_tgt, get_children);
+            let (node, _, count) = stack.pop().unwrap();
+            on_stack.remove(&node);
+            match stack.last_mut() {
+                Some((_, _, parent_count)) => add(parent_count, &count),
+                None => return Ok(count),
+            }
+            counts.insert(node, count);
         }
     }
-    count
+
+    unreachable!("the source is only popped off the stack by returning")
+}
+
+/// Outputs the number of paths from a source to a target in an **unweighted**
+/// graph, where a path ends at the first target it reaches.
+///
+/// The number of paths from each node is only counted once, so this takes
+/// time linear in the size of the graph however many paths there are.
+///
+/// # Errors
+///
+/// Returns a cycle if one can be reached from the source without passing
+/// through a target, as there may then be infinitely many paths.
+pub fn count_paths<T, F1, F2>(src: T, is_tgt: &F1, get_children: &F2) -> Result<u128, Cycle<T>>
+where
+    T: Eq + Hash + Copy + Debug,
+    F1: Fn(T) -> bool,
+    F2: Fn(T) -> Vec<T>,
+{
+    count_paths_with(
+        src,
+        is_tgt,
+        get_children,
+        |_| 1,
+        |count, other| {
+            *count += other;
+        },
+    )
+}
+
+/// Outputs the number of paths from a source to each target it can reach in
+/// an **unweighted** graph, where a path ends at the first target it reaches
+/// (see [`count_paths`]).
+///
+/// # Errors
+///
+/// Returns a cycle if one can be reached from the source without passing
+/// through a target.
+pub fn count_paths_per_target<T, F1, F2>(
+    src: T,
+    is_tgt: &F1,
+    get_children: &F2,
+) -> Result<HashMap<T, u128>, Cycle<T>>
+where
+    T: Eq + Hash + Copy + Debug,
+    F1: Fn(T) -> bool,
+    F2: Fn(T) -> Vec<T>,
+{
+    count_paths_with(
+        src,
+        is_tgt,
+        get_children,
+        |tgt| HashMap::from([(tgt, 1)]),
+        |counts, other| {
+            for (&tgt, &count) in other {
+                *counts.entry(tgt).or_default() += count;
+            }
+        },
+    )
 }
 
 /// Outputs the cost of a shortest path from a source node to a target node in
 /// a **weighted** graph.
-pub fn shortest_path_cost<T, F1, F2>(src: T, is_tgt: F1, get_children: &mut F2) -> Option<usize>
+pub fn shortest_path_cost<T, C, F1, F2>(src: T, is_tgt: F1, get_children: &mut F2) -> Option<C>
 where
     T: Eq + Hash + Copy + Debug + Ord,
+    C: Cost,
     F1: Fn(T) -> bool,
-    F2: FnMut(T) -> Vec<(usize, T)>,
+    F2: FnMut(T) -> Vec<(C, T)>,
 {
     if is_tgt(src) {
-        return Some(0);
+        return Some(C::zero());
     }
 
-    let mut pq: BinaryHeap<(Reverse<usize>, T)> = BinaryHeap::new();
-    let mut dist: HashMap<T, usize> = HashMap::new();
+    let mut pq: BinaryHeap<(Reverse<C>, T)> = BinaryHeap::new();
+    let mut dist: HashMap<T, C> = HashMap::new();
     let mut removed_from_pq: HashSet<T> = HashSet::new();
 
-    pq.push((Reverse(usize::MIN), src));
-    dist.insert(src, usize::MIN);
+    pq.push((Reverse(C::zero()), src));
+    dist.insert(src, C::zero());
 
     while !pq.is_empty() {
         if let Some((Reverse(distance), u)) = pq.pop() {
@@ -445,8 +590,7 @@ where
 
             for (weight, nbr) in get_children(u) {
                 if removed_from_pq.contains(&nbr) {
-                } else {
-                    let alt = distance + weight;
+                } else if let Some(alt) = distance.checked_add_cost(weight) {
                     if !dist.contains_key(&nbr) || alt < dist[&nbr] {
                         dist.insert(nbr, alt);
                         pq.push((Reverse(alt), nbr));
@@ -459,8 +603,550 @@ where
     None
 }
 
+/// The graph made of some nodes and every node reachable from them, numbered
+/// in the order they were given or found in, with each node's children listed
+/// by number.
+struct Indexed<T> {
+    nodes: Vec<T>,
+    children: Vec<Vec<usize>>,
+}
+
+impl<T: Eq + Hash + Copy> Indexed<T> {
+    fn new<F>(nodes: &[T], get_children: F) -> Self
+    where
+        F: Fn(T) -> Vec<T>,
+    {
+        let mut index: HashMap<T, usize> = HashMap::new();
+        let mut all = Vec::new();
+        for &node in nodes {
+            if let Vacant(e) = index.entry(node) {
+                e.insert(all.len());
+                all.push(node);
+            }
+        }
+
+        let mut children = Vec::new();
+        while children.len() < all.len() {
+            let node = all[children.len()];
+            let mut numbers = Vec::new();
+            for child in get_children(node) {
+                let number = *index.entry(child).or_insert_with(|| {
+                    all.push(child);
+                    all.len() - 1
+                });
+                numbers.push(number);
+            }
+            children.push(numbers);
+        }
+
+        Self {
+            nodes: all,
+            children,
+        }
+    }
+
+    /// Outputs the nodes in reverse postorder of a depth-first search, which
+    /// is a topological order, or the numbers of a cycle if there is one.
+    fn dfs_order(&self) -> Result<Vec<usize>, Vec<usize>> {
+        const UNVISITED: u8 = 0;
+        const ON_STACK: u8 = 1;
+        const DONE: u8 = 2;
+
+        let mut state = vec![UNVISITED; self.nodes.len()];
+        let mut postorder = Vec::with_capacity(self.nodes.len());
+
+        // Roots and children are visited last to first, so that reversing the
+        // postorder leaves nodes that are not ordered by any edge in the order
+        // they were given.
+        for root in (0..self.nodes.len()).rev() {
+            if state[root] != UNVISITED {
+                continue;
+            }
+
+            // Each node on the path being explored, along with the number of
+            // its children left to explore.
+            let mut stack = vec![(root, self.children[root].len())];
+            state[root] = ON_STACK;
+
+            while let Some((node, left)) = stack.last_mut() {
+                let node = *node;
+                if *left == 0 {
+                    state[node] = DONE;
+                    postorder.push(node);
+                    stack.pop();
+                    continue;
+                }
+
+                *left -= 1;
+                let child = self.children[node][*left];
+                match state[child] {
+                    UNVISITED => {
+                        state[child] = ON_STACK;
+                        stack.push((child, self.children[child].len()));
+                    }
+                    ON_STACK => {
+                        return Err(stack
+                            .iter()
+                            .map(|&(node, _)| node)
+                            .skip_while(|&node| node != child)
+                            .collect());
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        postorder.reverse();
+        Ok(postorder)
+    }
+
+    fn to_nodes(&self, numbers: Vec<usize>) -> Vec<T> {
+        numbers.into_iter().map(|i| self.nodes[i]).collect()
+    }
+}
+
+/// Outputs the given nodes and every node reachable from them in topological
+/// order, so that each node comes before its children, using Kahn's
+/// algorithm.
+///
+/// The order is stable: whenever several nodes could come next, the first of
+/// them in `nodes` does, followed by those found from them in the order they
+/// were found. In particular, nodes which are already in topological order
+/// are output unchanged.
+///
+/// # Errors
+///
+/// Returns a cycle if there is one, as the nodes then have no topological
+/// order.
+pub fn topo_sort<T, F>(nodes: &[T], get_children: F) -> Result<Vec<T>, Cycle<T>>
+where
+    T: Eq + Hash + Copy + Debug,
+    F: Fn(T) -> Vec<T>,
+{
+    let graph = Indexed::new(nodes, get_children);
+
+    let mut in_degree = vec![0_usize; graph.nodes.len()];
+    for &child in graph.children.iter().flatten() {
+        in_degree[child] += 1;
+    }
+
+    // The nodes whose parents have all been output, earliest first.
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..graph.nodes.len())
+        .filter(|&i| in_degree[i] == 0)
+        .map(Reverse)
+        .collect();
+    let mut order = Vec::with_capacity(graph.nodes.len());
+
+    while let Some(Reverse(i)) = ready.pop() {
+        order.push(i);
+        for &child in &graph.children[i] {
+            in_degree[child] -= 1;
+            if in_degree[child] == 0 {
+                ready.push(Reverse(child));
+            }
+        }
+    }
+
+    if order.len() == graph.nodes.len() {
+        return Ok(graph.to_nodes(order));
+    }
+
+    // Some nodes were never ready, so lie on or after a cycle, which the
+    // depth-first search is then sure to find.
+    match graph.dfs_order() {
+        Err(cycle) => Err(Cycle(graph.to_nodes(cycle))),
+        Ok(_) => unreachable!("Kahn's algorithm only gets stuck on a cycle"),
+    }
+}
+
+/// Outputs the given nodes and every node reachable from them in topological
+/// order, so that each node comes before its children, using a depth-first
+/// search.
+///
+/// The order is stable, in that nodes and children are always visited in the
+/// order given, and nodes which no edge orders keep their order in `nodes`.
+/// Where several orders are valid, it may differ from that of [`topo_sort`].
+///
+/// # Errors
+///
+/// Returns a cycle if there is one, as the nodes then have no topological
+/// order.
+pub fn topo_sort_dfs<T, F>(nodes: &[T], get_children: F) -> Result<Vec<T>, Cycle<T>>
+where
+    T: Eq + Hash + Copy + Debug,
+    F: Fn(T) -> Vec<T>,
+{
+    let graph = Indexed::new(nodes, get_children);
+    graph
+        .dfs_order()
+        .map(|order| graph.to_nodes(order))
+        .map_err(|cycle| Cycle(graph.to_nodes(cycle)))
+}
+
+/// The strongly connected components of a graph, along with the graph they
+/// form, which has no cycles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condensation<T: Eq + Hash> {
+    /// The components, each made of nodes which can all reach each other, in
+    /// topological order: every edge between two components goes from the
+    /// earlier one to the later one. Nodes are listed in the order they were
+    /// given or found in.
+    pub components: Vec<Vec<T>>,
+    /// The children of each component in the condensed graph, by index into
+    /// `components`, in increasing order and without repeats.
+    pub children: Vec<Vec<usize>>,
+    component: HashMap<T, usize>,
+}
+
+impl<T: Eq + Hash + Copy> Condensation<T> {
+    /// Outputs the condensation of an indexed graph, given the numbers of the
+    /// nodes in each of its components, in topological order.
+    fn new(graph: &Indexed<T>, components: Vec<Vec<usize>>) -> Self {
+        let mut component = vec![0; graph.nodes.len()];
+        for (c, members) in components.iter().enumerate() {
+            for &node in members {
+                component[node] = c;
+            }
+        }
+
+        let children = components
+            .iter()
+            .enumerate()
+            .map(|(c, members)| {
+                let mut children: Vec<usize> = members
+                    .iter()
+                    .flat_map(|&node| &graph.children[node])
+                    .map(|&child| component[child])
+                    .filter(|&other| other != c)
+                    .collect();
+                children.sort_unstable();
+                children.dedup();
+                children
+            })
+            .collect();
+
+        let components = components
+            .into_iter()
+            .map(|mut members| {
+                members.sort_unstable();
+                graph.to_nodes(members)
+            })
+            .collect();
+
+        Self {
+            components,
+            children,
+            component: graph.nodes.iter().copied().zip(component).collect(),
+        }
+    }
+
+    /// Outputs the index of the component containing a node, if it is in the
+    /// graph.
+    #[must_use]
+    pub fn component_of(&self, node: T) -> Option<usize> {
+        self.component.get(&node).copied()
+    }
+}
+
+impl<T: Eq + Hash + Copy> Indexed<T> {
+    /// Outputs the numbers of the nodes in each strongly connected component,
+    /// in topological order, using Tarjan's algorithm.
+    fn tarjan(&self) -> Vec<Vec<usize>> {
+        const UNVISITED: usize = usize::MAX;
+
+        let n = self.nodes.len();
+        let mut index = vec![UNVISITED; n];
+        let mut lowlink = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut next = 0;
+
+        for root in 0..n {
+            if index[root] != UNVISITED {
+                continue;
+            }
+
+            index[root] = next;
+            lowlink[root] = next;
+            next += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            // Each node on the path being explored, along with the position of
+            // its next child to explore.
+            let mut path = vec![(root, 0)];
+
+            while let Some((node, pos)) = path.last_mut() {
+                let node = *node;
+                if let Some(&child) = self.children[node].get(*pos) {
+                    *pos += 1;
+                    if index[child] == UNVISITED {
+                        index[child] = next;
+                        lowlink[child] = next;
+                        next += 1;
+                        stack.push(child);
+                        on_stack[child] = true;
+                        path.push((child, 0));
+                    } else if on_stack[child] {
+                        lowlink[node] = lowlink[node].min(index[child]);
+                    }
+                    continue;
+                }
+
+                path.pop();
+                if let Some(&(parent, _)) = path.last() {
+                    lowlink[parent] = lowlink[parent].min(lowlink[node]);
+                }
+
+                // The node is the first of its component to be visited, so the
+                // rest of the component lies above it on the stack.
+                if lowlink[node] == index[node] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+
+        // Components are completed after every component they can reach.
+        components.reverse();
+        components
+    }
+
+    /// Outputs the numbers of the nodes in each strongly connected component,
+    /// in topological order, using Kosaraju's algorithm.
+    fn kosaraju(&self) -> Vec<Vec<usize>> {
+        let n = self.nodes.len();
+
+        // Nodes in the order a depth-first search finishes with them.
+        let mut visited = vec![false; n];
+        let mut finished = Vec::with_capacity(n);
+        for root in 0..n {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+
+            let mut path = vec![(root, 0)];
+            while let Some((node, pos)) = path.last_mut() {
+                let node = *node;
+                if let Some(&child) = self.children[node].get(*pos) {
+                    *pos += 1;
+                    if !visited[child] {
+                        visited[child] = true;
+                        path.push((child, 0));
+                    }
+                } else {
+                    finished.push(node);
+                    path.pop();
+                }
+            }
+        }
+
+        let mut parents = vec![Vec::new(); n];
+        for (node, children) in self.children.iter().enumerate() {
+            for &child in children {
+                parents[child].push(node);
+            }
+        }
+
+        // Whatever reaches the last node to finish, among those left, is its
+        // component.
+        let mut assigned = vec![false; n];
+        let mut components = Vec::new();
+        for &root in finished.iter().rev() {
+            if assigned[root] {
+                continue;
+            }
+            assigned[root] = true;
+
+            let mut component = vec![root];
+            let mut i = 0;
+            while let Some(&node) = component.get(i) {
+                i += 1;
+                for &parent in &parents[node] {
+                    if !assigned[parent] {
+                        assigned[parent] = true;
+                        component.push(parent);
+                    }
+                }
+            }
+            components.push(component);
+        }
+
+        components
+    }
+}
+
+/// Outputs the strongly connected components of the graph made of the given
+/// nodes and every node reachable from them, along with the graph they form,
+/// using Tarjan's algorithm.
+///
+/// The condensed graph has no cycles, and its components are already in
+/// topological order, so cyclic dependencies can be resolved by handling each
+/// component as a whole, in order.
+pub fn tarjan_scc<T, F>(nodes: &[T], get_children: F) -> Condensation<T>
+where
+    T: Eq + Hash + Copy + Debug,
+    F: Fn(T) -> Vec<T>,
+{
+    let graph = Indexed::new(nodes, get_children);
+    let components = graph.tarjan();
+    Condensation::new(&graph, components)
+}
+
+/// Outputs the strongly connected components of the graph made of the given
+/// nodes and every node reachable from them, along with the graph they form,
+/// using Kosaraju's algorithm (see [`tarjan_scc`]).
+///
+/// The components are the same as those found by [`tarjan_scc`], though where
+/// several topological orders are valid they may be listed in another.
+pub fn kosaraju_scc<T, F>(nodes: &[T], get_children: F) -> Condensation<T>
+where
+    T: Eq + Hash + Copy + Debug,
+    F: Fn(T) -> Vec<T>,
+{
+    let graph = Indexed::new(nodes, get_children);
+    let components = graph.kosaraju();
+    Condensation::new(&graph, components)
+}
+
+/// The cost of reaching each node found by a search, along with its
+/// predecessor on a cheapest path there.
+type Predecessors<T, C> = HashMap<T, (C, Option<T>)>;
+
+/// Searches a **weighted** graph for a cheapest path from a source to a target
+/// with A*, outputting the target reached along with what the search found.
+fn astar_search<T, C, F1, F2, F3>(
+    src: T,
+    is_tgt: &F1,
+    get_children: &mut F2,
+    heuristic: &F3,
+) -> Option<(T, Predecessors<T, C>)>
+where
+    T: Eq + Hash + Copy + Debug + Ord,
+    C: Cost,
+    F1: Fn(T) -> bool,
+    F2: FnMut(T) -> Vec<(C, T)>,
+    F3: Fn(T) -> C,
+{
+    // Entries are (estimated cost of a path through the node, cost of reaching
+    // it, node), so that the node whose path looks cheapest is expanded first.
+    let mut pq: BinaryHeap<(Reverse<C>, Reverse<C>, T)> = BinaryHeap::new();
+    let mut best: Predecessors<T, C> = HashMap::new();
+
+    pq.push((Reverse(heuristic(src)), Reverse(C::zero()), src));
+    best.insert(src, (C::zero(), None));
+
+    while let Some((_, Reverse(cost), u)) = pq.pop() {
+        // Skip nodes that were reached more cheaply since they were queued.
+        if cost > best[&u].0 {
+            continue;
+        }
+
+        if is_tgt(u) {
+            return Some((u, best));
+        }
+
+        for (weight, nbr) in get_children(u) {
+            let Some(alt) = cost.checked_add_cost(weight) else {
+                continue;
+            };
+            if best.get(&nbr).is_none_or(|&(known, _)| alt < known) {
+                best.insert(nbr, (alt, Some(u)));
+                if let Some(estimate) = alt.checked_add_cost(heuristic(nbr)) {
+                    pq.push((Reverse(estimate), Reverse(alt), nbr));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Outputs a cheapest path from a source to a target in a **weighted** graph,
+/// along with the cost of reaching each node on it, using A* search.
+///
+/// The heuristic estimates the cost of reaching a target from a node. It must
+/// never overestimate it, or the path found may not be a cheapest one, but the
+/// closer it is the fewer nodes are expanded. With a heuristic of zero this is
+/// the same search as [`shortest_path_weighted`].
+pub fn astar<T, C, F1, F2, F3>(
+    src: T,
+    is_tgt: &F1,
+    get_children: &mut F2,
+    heuristic: &F3,
+) -> Option<Vec<(T, C)>>
+where
+    T: Eq + Hash + Copy + Debug + Ord,
+    C: Cost,
+    F1: Fn(T) -> bool,
+    F2: FnMut(T) -> Vec<(C, T)>,
+    F3: Fn(T) -> C,
+{
+    let (tgt, best) = astar_search(src, is_tgt, get_children, heuristic)?;
+
+    let mut path = VecDeque::new();
+    let mut cur = Some(tgt);
+    while let Some(u) = cur {
+        let (cost, parent) = best[&u];
+        path.push_front((u, cost));
+        cur = parent;
+    }
+    Some(path.into_iter().collect())
+}
+
+/// Outputs the cost of a cheapest path from a source to a target in a
+/// **weighted** graph, using A* search with the given heuristic (see
+/// [`astar`]).
+pub fn astar_cost<T, C, F1, F2, F3>(
+    src: T,
+    is_tgt: &F1,
+    get_children: &mut F2,
+    heuristic: &F3,
+) -> Option<C>
+where
+    T: Eq + Hash + Copy + Debug + Ord,
+    C: Cost,
+    F1: Fn(T) -> bool,
+    F2: FnMut(T) -> Vec<(C, T)>,
+    F3: Fn(T) -> C,
+{
+    let (tgt, best) = astar_search(src, is_tgt, get_children, heuristic)?;
+    Some(best[&tgt].0)
+}
+
+/// Outputs the Manhattan distance between two positions in a grid: a
+/// heuristic for [`astar`] that never overestimates when moving one step
+/// costs at least 1 and steps are only taken orthogonally.
+#[must_use]
+pub const fn manhattan(a: Coords, b: Coords) -> usize {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+/// Outputs the Chebyshev distance between two positions in a grid: a
+/// heuristic for [`astar`] that never overestimates when moving one step
+/// costs at least 1, even if steps are also taken diagonally.
+#[must_use]
+pub const fn chebyshev(a: Coords, b: Coords) -> usize {
+    let rows = a.0.abs_diff(b.0);
+    let cols = a.1.abs_diff(b.1);
+    if rows > cols {
+        rows
+    } else {
+        cols
+    }
+}
+
 /// Outputs all nodes that
-pub fn get_nodes_in_cheapest_paths<T, F1, F2, F3>(
+pub fn get_nodes_in_cheapest_paths<T, C, F1, F2, F3>(
     src: T,
     is_tgt: &mut F1,
     get_children: &mut F2,
@@ -468,9 +1154,10 @@ pub fn get_nodes_in_cheapest_paths<T, F1, F2, F3>(
 ) -> Option<HashSet<T>>
 where
     T: Eq + Hash + Copy + Debug + Ord,
+    C: Cost,
     F1: FnMut(T) -> bool,
-    F2: FnMut(T) -> Vec<(usize, T)>,
-    F3: FnMut(T) -> Vec<(usize, T)>,
+    F2: FnMut(T) -> Vec<(C, T)>,
+    F3: FnMut(T) -> Vec<(C, T)>,
 {
     let dist = dijkstra(src, get_children);
     let nodes: Vec<_> = dist.keys().collect();
@@ -508,7 +1195,7 @@ where
         for t in &nodes {
             if let Some(src_to_t) = dist.get(t) {
                 if let Some(tgt_to_t) = rev_dist.get(t) {
-                    if *src_to_t + *tgt_to_t == min_cost {
+                    if src_to_t.checked_add_cost(*tgt_to_t) == Some(min_cost) {
                         output.insert(**t);
                     }
                 }
@@ -524,12 +1211,12 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_num_paths() {
+    fn test_count_paths() {
         // 0 1 3
         // 2   5
         // 4 6 7 8
         //     9
-        let mut get_children = |x: u8| match x {
+        let get_children = |x: u8| match x {
             0 => vec![1, 2],
             1 => vec![3],
             2 => vec![4],
@@ -542,7 +1229,159 @@ mod tests {
 
         let is_tgt = |x: u8| x >= 8;
 
-        assert_eq!(num_paths(0, &is_tgt, &mut get_children), 4);
+        assert_eq!(count_paths(0, &is_tgt, &get_children), Ok(4));
+        assert_eq!(
+            count_paths_per_target(0, &is_tgt, &get_children),
+            Ok(HashMap::from([(8, 2), (9, 2)]))
+        );
+        assert_eq!(count_paths(8, &is_tgt, &get_children), Ok(1));
+
+        // A chain of 100 diamonds, each doubling the number of paths.
+        let get_children = |x: u16| match x % 3 {
+            0 => vec![x + 1, x + 2],
+            _ => vec![x + 3 - x % 3],
+        };
+        assert_eq!(count_paths(0, &|x| x == 300, &get_children), Ok(1 << 100));
+
+        // 0 -> 1 -> 2 -> 3 -> 1, with 3 also leading to the target 4.
+        let get_children = |x: u8| match x {
+            0 => vec![1],
+            1 => vec![2],
+            2 => vec![3],
+            3 => vec![4, 1],
+            _ => Vec::new(),
+        };
+        assert_eq!(
+            count_paths(0, &|x| x == 4, &get_children),
+            Err(Cycle(vec![1, 2, 3]))
+        );
+        assert_eq!(count_paths(0, &|x| x == 2, &get_children), Ok(1));
+    }
+
+    #[test]
+    fn test_topo_sort() {
+        // 5 -> 0 <- 4
+        // |         |
+        // v         v
+        // 2 -> 3 -> 1
+        let get_children = |x: u8| match x {
+            5 => vec![2, 0],
+            4 => vec![0, 1],
+            2 => vec![3],
+            3 => vec![1],
+            _ => Vec::new(),
+        };
+
+        let is_topological = |order: &[u8]| {
+            order.iter().enumerate().all(|(i, &x)| {
+                get_children(x)
+                    .iter()
+                    .all(|child| order[i + 1..].contains(child))
+            })
+        };
+
+        for sort in [topo_sort, topo_sort_dfs] {
+            let order = sort(&[0, 1, 2, 3, 4, 5], get_children).unwrap();
+            assert_eq!(order.len(), 6);
+            assert!(is_topological(&order), "{order:?}");
+
+            // Nodes found from the given ones are sorted along with them.
+            let order = sort(&[5], get_children).unwrap();
+            assert_eq!(order.len(), 5);
+            assert!(is_topological(&order), "{order:?}");
+
+            // Orders which are already topological, or not constrained at
+            // all, are kept.
+            assert_eq!(
+                sort(&[4, 5, 2, 3, 1, 0], get_children),
+                Ok(vec![4, 5, 2, 3, 1, 0])
+            );
+            assert_eq!(sort(&[9, 7, 8], get_children), Ok(vec![9, 7, 8]));
+        }
+
+        assert_eq!(
+            topo_sort(&[0, 1, 2, 3, 4, 5], get_children),
+            Ok(vec![4, 5, 0, 2, 3, 1])
+        );
+    }
+
+    #[test]
+    fn test_topo_sort_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 1, and 3 -> 4.
+        let get_children = |x: u8| match x {
+            0 => vec![1],
+            1 => vec![2],
+            2 => vec![3],
+            3 => vec![4, 1],
+            _ => Vec::new(),
+        };
+
+        for sort in [topo_sort, topo_sort_dfs] {
+            let Err(Cycle(cycle)) = sort(&[0, 4], get_children) else {
+                panic!("the cycle went unnoticed");
+            };
+            assert_eq!(cycle.len(), 3);
+            for (i, &x) in cycle.iter().enumerate() {
+                assert!(get_children(x).contains(&cycle[(i + 1) % cycle.len()]));
+            }
+        }
+
+        assert_eq!(topo_sort(&[7], |x: u8| vec![x]), Err(Cycle(vec![7])));
+    }
+
+    #[test]
+    fn test_scc() {
+        // 0 -> 1 -> 2 -> 0,  3 -> 4 -> 5 -> 3,  6 <-> 7,  8 alone, with the
+        // edges 2 -> 3, 6 -> 5 and 7 -> 8 between them.
+        let get_children = |x: u8| match x {
+            0 => vec![1],
+            1 => vec![2],
+            2 => vec![0, 3],
+            3 => vec![4],
+            4 => vec![5],
+            5 => vec![3],
+            6 => vec![5, 7],
+            7 => vec![6, 8],
+            _ => Vec::new(),
+        };
+
+        for scc in [tarjan_scc, kosaraju_scc] {
+            let condensation = scc(&[6, 0], get_children);
+
+            let mut components = condensation.components.clone();
+            for component in &mut components {
+                component.sort_unstable();
+            }
+            components.sort_unstable();
+            assert_eq!(
+                components,
+                [vec![0, 1, 2], vec![3, 4, 5], vec![6, 7], vec![8]]
+            );
+
+            let of = |x| condensation.component_of(x).unwrap();
+            assert_eq!(condensation.children[of(0)], [of(3)]);
+            let mut children = condensation.children[of(6)].clone();
+            children.sort_unstable();
+            let mut expected = vec![of(3), of(8)];
+            expected.sort_unstable();
+            assert_eq!(children, expected);
+            assert!(condensation.children[of(3)].is_empty());
+            assert_eq!(condensation.component_of(9), None);
+
+            // Components are in topological order, which topo_sort keeps.
+            for (c, children) in condensation.children.iter().enumerate() {
+                assert!(children.iter().all(|&child| child > c));
+            }
+            let order: Vec<usize> = (0..condensation.components.len()).collect();
+            assert_eq!(
+                topo_sort(&order, |c| condensation.children[c].clone()),
+                Ok(order)
+            );
+        }
+
+        let condensation = tarjan_scc(&[7], |x: u8| vec![x]);
+        assert_eq!(condensation.components, [vec![7]]);
+        assert_eq!(condensation.children, [Vec::<usize>::new()]);
     }
 
     #[test]
@@ -631,9 +1470,11 @@ mod tests {
         assert_eq!(output, None);
     }
 
-    #[test]
-    fn w_test_shortest_path() {
-        let mut get_children = |x: u8| match x {
+    /// Children of each node of a weighted graph made of a cycle through 0 to 5,
+    /// each step costing 1, and a triangle of 6, 7 and 8 where the direct edges
+    /// between 6 and 7 cost more than going through 8.
+    fn weighted_fixture(x: u8) -> Vec<(usize, u8)> {
+        match x {
             0 => vec![(1, 1), (1, 5)],
             1 => vec![(1, 0), (1, 2)],
             2 => vec![(1, 1), (1, 3)],
@@ -644,7 +1485,12 @@ mod tests {
             7 => vec![(10, 6), (5, 8)],
             8 => vec![(1, 6), (5, 7)],
             _ => Vec::new(),
-        };
+        }
+    }
+
+    #[test]
+    fn w_test_shortest_path() {
+        let mut get_children = weighted_fixture;
 
         assert_eq!(
             shortest_path_weighted(0, &|x| x == 5, &mut get_children),
@@ -672,12 +1518,117 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_astar() {
+        let mut get_children = weighted_fixture;
+
+        // Admissible estimates of the cost to 7, and to 3, from each node.
+        let to_7 = |x: u8| match x {
+            6 => 6,
+            8 => 5,
+            _ => 0,
+        };
+        let to_3 = |x: u8| (3_u8.abs_diff(x).min(6 - 3_u8.abs_diff(x))).into();
+
+        for (src, tgt) in [(0, 5), (0, 4), (0, 2), (0, 0), (6, 7), (0, 3), (5, 3)] {
+            let is_tgt = |x| x == tgt;
+            let heuristic = |x| {
+                if tgt == 7 {
+                    to_7(x)
+                } else if tgt == 3 {
+                    to_3(x)
+                } else {
+                    0
+                }
+            };
+
+            let path = astar(src, &is_tgt, &mut get_children, &heuristic);
+            let expected = shortest_path_weighted(src, &is_tgt, &mut get_children);
+            assert_eq!(
+                path.as_ref().map(|path| path.last().unwrap().1),
+                expected.as_ref().map(|path| path.last().unwrap().1)
+            );
+            assert_eq!(path.as_ref().map(Vec::len), expected.as_ref().map(Vec::len));
+            assert_eq!(
+                astar_cost(src, &is_tgt, &mut get_children, &heuristic),
+                shortest_path_cost(src, is_tgt, &mut get_children)
+            );
+        }
+
+        assert_eq!(
+            astar(6, &|x| x == 7, &mut get_children, &to_7),
+            Some(vec![(6, 0), (8, 1), (7, 6)])
+        );
+        assert_eq!(astar_cost(0, &|x| x == 7, &mut get_children, &|_| 0), None);
+    }
+
+    #[test]
+    fn test_heuristics() {
+        assert_eq!(manhattan((1, 5), (4, 1)), 7);
+        assert_eq!(chebyshev((1, 5), (4, 1)), 4);
+        assert_eq!(manhattan((2, 2), (2, 2)), 0);
+    }
+
     #[test]
     fn w_test_shortest_path_no_path() {
         assert_eq!(
-            shortest_path_weighted(0, &|x| x == 1, &mut |_| Vec::new()),
+            shortest_path_weighted(0, &|x| x == 1, &mut |_| Vec::<(usize, u8)>::new()),
             None
         );
-        assert_eq!(shortest_path_cost(0, |x| x == 1, &mut |_| Vec::new()), None);
+        assert_eq!(
+            shortest_path_cost(0, |x| x == 1, &mut |_| Vec::<(usize, u8)>::new()),
+            None
+        );
+    }
+
+    #[test]
+    fn w_test_shortest_path_costs() {
+        // 0 -> 1 -> 3 costs 5/2, and 0 -> 2 -> 3 costs 3.
+        let mut get_children = |x: u8| match x {
+            0 => vec![(Ratio::new(1, 2), 1), (Ratio::new(1, 1), 2)],
+            1 | 2 => vec![(Ratio::new(2, 1), 3)],
+            _ => Vec::new(),
+        };
+        assert_eq!(
+            shortest_path_cost(0, |x| x == 3, &mut get_children),
+            Some(Ratio::new(5, 2))
+        );
+        assert_eq!(
+            shortest_path_weighted(0, &|x| x == 3, &mut get_children),
+            Some(vec![
+                (0, Ratio::from_integer(0)),
+                (1, Ratio::new(1, 2)),
+                (3, Ratio::new(5, 2))
+            ])
+        );
+
+        let mut get_children = |x: u8| match x {
+            0 => vec![(1_i64, 1), (5, 2)],
+            1 => vec![(3, 2)],
+            _ => Vec::new(),
+        };
+        assert_eq!(dijkstra(0, &mut get_children)[&2], 4);
+
+        // The path through 1 would overflow, so the dearer one through 2 is
+        // taken instead.
+        let mut get_children = |x: u8| match x {
+            0 => vec![(200_u8, 1), (250, 2)],
+            1 => vec![(100, 3)],
+            2 => vec![(5, 3)],
+            _ => Vec::new(),
+        };
+        assert_eq!(
+            shortest_path_cost(0, |x| x == 3, &mut get_children),
+            Some(255)
+        );
+        assert_eq!(
+            get_nodes_in_cheapest_paths(0, &mut |x| x == 3, &mut get_children, &mut |x| match x {
+                3 => vec![(100, 1), (5, 2)],
+                1 => vec![(200, 0)],
+                2 => vec![(250, 0)],
+                _ => Vec::new(),
+            }),
+            Some(HashSet::from([0, 2, 3]))
+        );
     }
 }
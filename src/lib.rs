@@ -1,10 +1,21 @@
+pub mod answers;
+pub mod bench;
 pub mod bimap;
+pub mod cli;
 pub mod common; // or any modules you want to expose
 pub mod days;
 pub mod direction;
+pub mod fetch;
 pub mod graph;
+pub mod log;
 pub mod memoizer;
+pub mod progress;
+pub mod repl;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
 pub mod uptree;
 pub mod utils;
+pub mod watch;
 
-pub use common::run_w_args; // expose function(s) used in tests
+pub use common::{run_on_str, run_w_args, RunError}; // expose function(s) used in tests
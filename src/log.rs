@@ -0,0 +1,94 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much is logged, from 0 (nothing) up; set once by the command line.
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+/// How detailed a log message is, and so how verbose logging must be for it
+/// to be printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Printed from `-v` up: what a solver is doing, a few lines per run.
+    Debug = 1,
+    /// Printed from `-vv` up: every step of a solver.
+    Trace = 2,
+}
+
+impl Level {
+    /// Outputs the tag messages of this level are printed with.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Debug => "debug",
+            Self::Trace => "trace",
+        }
+    }
+}
+
+/// Sets how much is logged from now on.
+pub fn set_verbosity(verbosity: u8) {
+    VERBOSITY.store(verbosity, Ordering::Relaxed);
+}
+
+#[must_use]
+pub fn verbosity() -> u8 {
+    VERBOSITY.load(Ordering::Relaxed)
+}
+
+/// Outputs whether messages of the given level are logged at the given
+/// verbosity, which they never are in release builds.
+#[must_use]
+pub const fn allows(verbosity: u8, level: Level) -> bool {
+    cfg!(debug_assertions) && verbosity >= level as u8
+}
+
+/// Outputs whether messages of the given level are currently logged, for
+/// guarding work that is only needed to log something.
+#[must_use]
+pub fn enabled(level: Level) -> bool {
+    allows(verbosity(), level)
+}
+
+/// Prints a message of the given level to stderr if it is enabled.
+///
+/// The arguments are only evaluated when the message is printed, and in
+/// release builds the whole call is compiled out, so logging can be left in
+/// solvers at no cost.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if cfg!(debug_assertions) && $crate::log::enabled($level) {
+            eprintln!("[{}] {}", $crate::log::Level::as_str($level), format_args!($($arg)+));
+        }
+    };
+}
+
+/// Logs a message printed from `-v` up (see [`log!`]).
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::log::Level::Debug, $($arg)+)
+    };
+}
+
+/// Logs a message printed from `-vv` up (see [`log!`]).
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::log::Level::Trace, $($arg)+)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allows() {
+        assert!(!allows(0, Level::Debug));
+        assert_eq!(allows(1, Level::Debug), cfg!(debug_assertions));
+        assert!(!allows(1, Level::Trace));
+        assert_eq!(allows(2, Level::Trace), cfg!(debug_assertions));
+        assert_eq!(allows(3, Level::Debug), cfg!(debug_assertions));
+        assert!(Level::Debug < Level::Trace);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::solution::Key;
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Minimum time between two reports of a part's progress.
+const INTERVAL: Duration = Duration::from_millis(100);
+
+/// Number of characters filled in by a complete progress bar.
+const BAR_WIDTH: usize = 30;
+
+/// How the progress of long-running parts is reported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProgressMode {
+    /// Not at all.
+    #[default]
+    Silent,
+    /// As a bar redrawn in place on stderr.
+    Bar,
+    /// As one JSON object per update on stderr.
+    Json,
+}
+
+impl ProgressMode {
+    /// Outputs the mode suited to where output is going: JSON events if
+    /// results are printed as JSON, a bar if stderr is a terminal, and nothing
+    /// otherwise.
+    #[must_use]
+    pub fn detect(json: bool) -> Self {
+        if json {
+            Self::Json
+        } else if io::stderr().is_terminal() {
+            Self::Bar
+        } else {
+            Self::Silent
+        }
+    }
+}
+
+/// Formats the progress bar of a part which has done `done` out of `total`
+/// steps.
+#[must_use]
+pub fn render_bar(key: Key, part: u8, done: usize, total: usize) -> String {
+    let (filled, hundredths) = if total == 0 {
+        (BAR_WIDTH, 10_000)
+    } else {
+        let done = done.min(total);
+        (done * BAR_WIDTH / total, done * 10_000 / total)
+    };
+
+    format!(
+        "{key} part {part} [{}{}] {}.{:02}%",
+        "#".repeat(filled),
+        " ".repeat(BAR_WIDTH - filled),
+        hundredths / 100,
+        hundredths % 100
+    )
+}
+
+/// Formats the progress of a part which has done `done` out of `total` steps
+/// as a single-line JSON object.
+#[must_use]
+pub fn progress_json(key: Key, part: u8, done: usize, total: usize) -> String {
+    format!(
+        "{{\"event\":\"progress\",\"year\":{},\"day\":{},\"part\":{part},\"done\":{done},\"total\":{total}}}",
+        key.year,
+        key.day
+    )
+}
+
+/// Handle through which a solver reports how far along it is.
+///
+/// Updates are rate limited, so solvers can report progress on every step
+/// without slowing down.
+pub struct Progress {
+    mode: ProgressMode,
+    key: Key,
+    part: u8,
+    /// When progress was last reported, if it has been.
+    last: Mutex<Option<Instant>>,
+    /// Set once the part is given up on, after which nothing is reported.
+    cancelled: Option<Arc<AtomicBool>>,
+}
+
+impl Progress {
+    #[must_use]
+    pub const fn new(mode: ProgressMode, key: Key, part: u8) -> Self {
+        Self {
+            mode,
+            key,
+            part,
+            last: Mutex::new(None),
+            cancelled: None,
+        }
+    }
+
+    /// Outputs the same handle, which stops reporting anything once
+    /// `cancelled` is set.
+    #[must_use]
+    pub fn cancelled_by(mut self, cancelled: Arc<AtomicBool>) -> Self {
+        self.cancelled = Some(cancelled);
+        self
+    }
+
+    /// Outputs whether the part was given up on, so that progress is no longer
+    /// reported.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+            .as_ref()
+            .is_some_and(|cancelled| cancelled.load(Ordering::Relaxed))
+    }
+
+    /// Outputs a handle which reports nothing.
+    #[must_use]
+    pub const fn silent() -> Self {
+        Self::new(ProgressMode::Silent, Key::new(0, 0), 0)
+    }
+
+    /// Outputs whether enough time passed since progress was last reported
+    /// to report it again, noting that it is if so.
+    fn is_due(&self) -> bool {
+        let mut last = self.last.lock().unwrap_or_else(PoisonError::into_inner);
+        if last.is_some_and(|last| last.elapsed() < INTERVAL) {
+            return false;
+        }
+        *last = Some(Instant::now());
+        true
+    }
+
+    /// Reports that `done` out of `total` steps are done.
+    pub fn update(&self, done: usize, total: usize) {
+        if self.mode == ProgressMode::Silent || self.is_cancelled() || !self.is_due() {
+            return;
+        }
+
+        let mut stderr = io::stderr().lock();
+        let _ = match self.mode {
+            ProgressMode::Silent => Ok(()),
+            ProgressMode::Bar => {
+                write!(stderr, "\r{}", render_bar(self.key, self.part, done, total))
+            }
+            ProgressMode::Json => writeln!(
+                stderr,
+                "{}",
+                progress_json(self.key, self.part, done, total)
+            ),
+        };
+        let _ = stderr.flush();
+    }
+
+    /// Clears the progress bar, if one was drawn. Called once the part is
+    /// done, so that solvers need not.
+    pub fn finish(&self) {
+        let drawn = self
+            .last
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_some();
+        if self.mode == ProgressMode::Bar && drawn && !self.is_cancelled() {
+            let mut stderr = io::stderr().lock();
+            let _ = write!(stderr, "\r\x1b[2K");
+            let _ = stderr.flush();
+        }
+    }
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Self::silent()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let key = Key::new(2024, 6);
+        assert_eq!(
+            render_bar(key, 2, 1, 2),
+            format!(
+                "2024 day 6 part 2 [{}{}] 50.00%",
+                "#".repeat(15),
+                " ".repeat(15)
+            )
+        );
+        assert!(render_bar(key, 2, 3, 2).ends_with(&format!("{}] 100.00%", "#".repeat(30))));
+        assert!(render_bar(key, 2, 0, 0).ends_with("100.00%"));
+
+        assert_eq!(
+            progress_json(key, 2, 1, 2),
+            "{\"event\":\"progress\",\"year\":2024,\"day\":6,\"part\":2,\"done\":1,\"total\":2}"
+        );
+    }
+
+    #[test]
+    fn test_silent() {
+        let progress = Progress::silent();
+        progress.update(1, 2);
+        progress.finish();
+        assert!(progress.last.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let progress =
+            Progress::new(ProgressMode::Json, Key::new(2024, 6), 2).cancelled_by(cancelled.clone());
+        assert!(!progress.is_cancelled());
+
+        cancelled.store(true, Ordering::Relaxed);
+        assert!(progress.is_cancelled());
+        progress.update(1, 2);
+        assert!(progress.last.lock().unwrap().is_none());
+    }
+}
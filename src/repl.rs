@@ -0,0 +1,465 @@
+use crate::bench::Stats;
+use crate::common::{
+    find_solution, format_duration, input_path, parse_day, run_isolated, RunError, RunOptions,
+};
+use crate::days;
+use crate::direction::{Coords, Direction};
+use crate::graph::shortest_path;
+use crate::solution::{ParsedInput, Solution};
+use crate::utils::{lines_from_str, lines_to_grid_of_chars};
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, BufRead, Write};
+
+/// Number of runs `time` makes unless told otherwise.
+const DEFAULT_ITERS: usize = 10;
+
+const HELP: &str = "\
+Commands:
+  day <day_number>          explore another day, loading its default input
+  input [path]              load an input file (the day's default input if none)
+  example <n>               load the day's nth worked example, with its parameters
+  part1 | part2 | both      solve parts on the loaded input
+  time <1 | 2> [iters]      time a part over several runs (10 by default)
+  params                    list the day's parameters and their values
+  set <name>=<value>        set a parameter
+  unset <name>              reset a parameter to its default
+  grid                      show the size of the input read as a grid of characters
+  find <char>               list where a character appears in the grid
+  path <from> <to> [walls]  find a shortest path through the grid between two
+                            characters or row,column pairs, avoiding the
+                            characters in walls (# by default)
+  help                      show this message
+  quit                      leave";
+
+fn bad_argument(message: &str) -> RunError {
+    RunError::BadArgument(message.to_string())
+}
+
+/// Parses an end of a path: either `row,column`, which must lie in the grid,
+/// or a character standing for its first occurrence in the grid.
+fn parse_end(grid: &[Vec<char>], end: &str) -> Result<Coords, RunError> {
+    if let Some((r, c)) = end.split_once(',') {
+        return match (r.parse::<usize>(), c.parse::<usize>()) {
+            (Ok(r), Ok(c)) if grid.get(r).is_some_and(|row| c < row.len()) => Ok((r, c)),
+            (Ok(_), Ok(_)) => Err(bad_argument(&format!("{end} is outside the grid"))),
+            _ => Err(bad_argument(&format!("position {end}"))),
+        };
+    }
+
+    let mut chars = end.chars();
+    let (Some(target), None) = (chars.next(), chars.next()) else {
+        return Err(bad_argument(&format!("position {end}")));
+    };
+    find(grid, target)
+        .first()
+        .copied()
+        .ok_or_else(|| bad_argument(&format!("{target} is not in the grid")))
+}
+
+/// Outputs the positions of a character in a grid, row by row.
+fn find(grid: &[Vec<char>], target: char) -> Vec<Coords> {
+    grid.iter()
+        .enumerate()
+        .flat_map(|(r, row)| {
+            row.iter()
+                .enumerate()
+                .filter(move |&(_, &c)| c == target)
+                .map(move |(c, _)| (r, c))
+        })
+        .collect()
+}
+
+fn format_coords((r, c): Coords) -> String {
+    format!("{r},{c}")
+}
+
+/// State of an interactive session: the day being explored, the input it is
+/// run on, and the values of its parameters.
+///
+/// The input is parsed once, the first time a part is solved, and reused
+/// until another input is loaded.
+pub struct Session {
+    year: u16,
+    /// Limits on each run, along with the parameters set.
+    options: RunOptions,
+    solution: Option<&'static dyn Solution>,
+    /// Text of the loaded input, if any.
+    text: Option<String>,
+    /// The loaded input as parsed by the day, once it has been.
+    input: Option<ParsedInput>,
+}
+
+impl Session {
+    #[must_use]
+    pub const fn new(year: u16, options: RunOptions) -> Self {
+        Self {
+            year,
+            options,
+            solution: None,
+            text: None,
+            input: None,
+        }
+    }
+
+    /// Runs a single command, outputting what it printed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the command is malformed, or running it fails.
+    pub fn execute(&mut self, line: &str) -> Result<String, RunError> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => Ok(String::new()),
+            ["help"] => Ok(HELP.to_string()),
+            ["day", day] => self.select_day(day),
+            ["input"] => {
+                let key = self.solution()?.key();
+                self.load_file(&input_path(key))
+            }
+            ["input", path] => self.load_file(path),
+            ["example", n] => self.load_example(n),
+            ["part1"] => self.solve(1),
+            ["part2"] => self.solve(2),
+            ["both"] => Ok(format!("{}\n{}", self.solve(1)?, self.solve(2)?)),
+            ["time", part] => self.time(part, DEFAULT_ITERS),
+            ["time", part, iters] => {
+                let iters = iters
+                    .parse()
+                    .map_err(|_| bad_argument(&format!("iters {iters}")))?;
+                self.time(part, iters)
+            }
+            ["params"] => self.describe_params(),
+            ["set", assignment] => match assignment.split_once('=') {
+                Some((name, value)) => self.set(name, value),
+                None => Err(bad_argument(&format!("set {assignment}"))),
+            },
+            ["unset", name] => {
+                self.check_param(name)?;
+                self.options.params.retain(|(other, _)| other != name);
+                Ok(String::new())
+            }
+            ["grid"] => {
+                let grid = self.grid()?;
+                let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+                Ok(format!("{} rows, {width} columns", grid.len()))
+            }
+            ["find", target] => {
+                let mut chars = target.chars();
+                let (Some(target), None) = (chars.next(), chars.next()) else {
+                    return Err(bad_argument(&format!("find {target}")));
+                };
+                let positions = find(&self.grid()?, target);
+                let listed: Vec<String> = positions.iter().copied().map(format_coords).collect();
+                Ok(format!("{} found: {}", positions.len(), listed.join(" ")))
+            }
+            ["path", from, to] => self.path(from, to, "#"),
+            ["path", from, to, walls] => self.path(from, to, walls),
+            _ => Err(bad_argument(&format!(
+                "{line:?} is not a command; try help"
+            ))),
+        }
+    }
+
+    fn solution(&self) -> Result<&'static dyn Solution, RunError> {
+        self.solution
+            .ok_or_else(|| bad_argument("no day chosen; try day <day_number>"))
+    }
+
+    /// Switches to another day, resetting the parameters, and loads its
+    /// default input if there is one.
+    fn select_day(&mut self, day: &str) -> Result<String, RunError> {
+        let key = parse_day(self.year, day)?;
+        let solution = days::registry()
+            .get(key)
+            .ok_or_else(|| RunError::BadDay(day.to_string()))?;
+
+        self.solution = Some(solution);
+        self.options.params.clear();
+        self.text = None;
+        self.input = None;
+
+        let loaded = self
+            .load_file(&input_path(key))
+            .unwrap_or_else(|err| err.to_string());
+        Ok(format!("{key}: {}\n{loaded}", solution.name()))
+    }
+
+    fn load(&mut self, text: String) {
+        self.text = Some(text);
+        self.input = None;
+    }
+
+    fn load_file(&mut self, path: &str) -> Result<String, RunError> {
+        let text = fs::read_to_string(path).map_err(|source| RunError::MissingInput {
+            path: path.to_string(),
+            source,
+        })?;
+        let message = format!("Loaded {path} ({} lines)", text.lines().count());
+        self.load(text);
+        Ok(message)
+    }
+
+    /// Loads a worked example of the day, numbered from 1, replacing the
+    /// parameters with those of the example.
+    fn load_example(&mut self, n: &str) -> Result<String, RunError> {
+        let examples = self.solution()?.examples();
+        let example = n
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| examples.get(n.checked_sub(1)?))
+            .ok_or_else(|| {
+                bad_argument(&format!(
+                    "example {n} (there are {} examples)",
+                    examples.len()
+                ))
+            })?;
+
+        self.options.params = example
+            .params
+            .iter()
+            .map(|&(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        self.load(example.input.to_string());
+
+        let expected = |part: u8| example.expected(part).unwrap_or("unknown");
+        Ok(format!(
+            "Loaded example {n} (expecting {} and {})",
+            expected(1),
+            expected(2)
+        ))
+    }
+
+    /// Runs a part on the loaded input, parsing it first if it has not been
+    /// yet, and outputs the answer along with how long this took.
+    fn solve(&mut self, part: u8) -> Result<String, RunError> {
+        let solution = find_solution(self.solution()?.key(), part)?;
+        let text = self
+            .text
+            .clone()
+            .ok_or_else(|| bad_argument("no input loaded; try input [path]"))?;
+
+        let attempt = run_isolated(
+            solution,
+            part,
+            self.input.take(),
+            move || Ok(lines_from_str(&text)),
+            self.options.clone(),
+        );
+        self.input = attempt.input;
+
+        let mut output = attempt.result?;
+        output.push_str("  (");
+        if let Some(parse) = attempt.parse_elapsed {
+            let _ = write!(output, "parse {}, ", format_duration(parse));
+        }
+        let _ = write!(output, "solve {})", format_duration(attempt.elapsed));
+        Ok(output)
+    }
+
+    /// Solves a part `iters` times, outputting statistics of the solve times.
+    fn time(&mut self, part: &str, iters: usize) -> Result<String, RunError> {
+        let part = match part {
+            "1" => 1,
+            "2" => 2,
+            _ => return Err(RunError::BadPart(part.to_string())),
+        };
+
+        // Parse ahead of timing, so that the first run is not slower.
+        self.solve(part)?;
+        let mut samples = Vec::with_capacity(iters);
+        for _ in 0..iters {
+            let solution = find_solution(self.solution()?.key(), part)?;
+            let text = self.text.clone().unwrap_or_default();
+            let attempt = run_isolated(
+                solution,
+                part,
+                self.input.take(),
+                move || Ok(lines_from_str(&text)),
+                self.options.clone(),
+            );
+            self.input = attempt.input;
+            attempt.result?;
+            samples.push(attempt.elapsed);
+        }
+
+        let stats = Stats::from_samples(&samples)
+            .ok_or_else(|| bad_argument("time needs at least 1 run"))?;
+        Ok(format!(
+            "min {}  median {}  mean {}  over {iters} runs",
+            format_duration(stats.min),
+            format_duration(stats.median),
+            format_duration(stats.mean)
+        ))
+    }
+
+    fn check_param(&self, name: &str) -> Result<(), RunError> {
+        let solution = self.solution()?;
+        if solution.params().iter().any(|param| param.name == name) {
+            Ok(())
+        } else {
+            Err(bad_argument(&format!(
+                "{} has no parameter {name}",
+                solution.key()
+            )))
+        }
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<String, RunError> {
+        self.check_param(name)?;
+        self.options.params.retain(|(other, _)| other != name);
+        self.options
+            .params
+            .push((name.to_string(), value.to_string()));
+        Ok(String::new())
+    }
+
+    fn describe_params(&self) -> Result<String, RunError> {
+        let solution = self.solution()?;
+        if solution.params().is_empty() {
+            return Ok(format!("{} has no parameters", solution.key()));
+        }
+
+        let lines: Vec<String> = solution
+            .params()
+            .iter()
+            .map(|param| {
+                let value = self
+                    .options
+                    .params
+                    .iter()
+                    .rev()
+                    .find(|(name, _)| name == param.name)
+                    .map_or_else(
+                        || format!("{} (default)", param.default),
+                        |(_, value)| value.clone(),
+                    );
+                format!("{} = {value}  {}", param.name, param.description)
+            })
+            .collect();
+        Ok(lines.join("\n"))
+    }
+
+    /// Outputs the loaded input read as a grid of characters.
+    fn grid(&self) -> Result<Vec<Vec<char>>, RunError> {
+        let text = self
+            .text
+            .as_deref()
+            .ok_or_else(|| bad_argument("no input loaded; try input [path]"))?;
+        Ok(lines_to_grid_of_chars(&mut lines_from_str(text)).collect())
+    }
+
+    /// Finds a shortest path through the grid, moving between orthogonally
+    /// adjacent positions that are not walls.
+    fn path(&self, from: &str, to: &str, walls: &str) -> Result<String, RunError> {
+        let grid = self.grid()?;
+        let src = parse_end(&grid, from)?;
+        let tgt = parse_end(&grid, to)?;
+
+        let height = grid.len();
+        let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+        let get_children = |x: Coords| {
+            let mut output = Vec::new();
+            for dir in [Direction::N, Direction::E, Direction::S, Direction::W] {
+                if let Some((r, c)) = dir.step_coords(x, height, width) {
+                    if grid[r].get(c).is_some_and(|c| !walls.contains(*c)) {
+                        output.push((r, c));
+                    }
+                }
+            }
+            output
+        };
+
+        Ok(shortest_path(src, tgt, get_children).map_or_else(
+            || "no path".to_string(),
+            |path| {
+                format!(
+                    "{} steps from {} to {}",
+                    path.len() - 1,
+                    format_coords(src),
+                    format_coords(tgt)
+                )
+            },
+        ))
+    }
+}
+
+/// Reads commands from stdin until it ends or `quit` is given, running each in
+/// the session and printing its output, or its error to stderr.
+///
+/// # Errors
+///
+/// Returns an error if stdin cannot be read or stdout written to.
+pub fn repl(mut session: Session) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    write!(stdout, "> ")?;
+    stdout.flush()?;
+
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        if matches!(line.trim(), "quit" | "exit") {
+            break;
+        }
+
+        match session.execute(&line) {
+            Ok(output) if output.is_empty() => {}
+            Ok(output) => writeln!(stdout, "{output}")?,
+            Err(err) => eprintln!("{err}"),
+        }
+        write!(stdout, "> ")?;
+        stdout.flush()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session() {
+        let mut session = Session::new(2024, RunOptions::default());
+        assert!(session.execute("part1").is_err());
+        assert!(session.execute("frobnicate").is_err());
+
+        let output = session.execute("day 20").unwrap();
+        assert!(
+            output.starts_with("2024 day 20: Race Condition"),
+            "{output}"
+        );
+        assert_eq!(
+            session.execute("example 1").unwrap(),
+            "Loaded example 1 (expecting 1 and 285)"
+        );
+        assert!(session.execute("part1").unwrap().starts_with("1  (parse "));
+
+        session.execute("set threshold=2").unwrap();
+        assert!(session.execute("part1").unwrap().starts_with("44  (solve "));
+        assert!(session.execute("set width=2").is_err());
+        let params = session.execute("params").unwrap();
+        assert!(params.starts_with("threshold = 2  "), "{params}");
+        assert!(params.contains("max_steps = 20 (default)"), "{params}");
+
+        assert_eq!(session.execute("grid").unwrap(), "15 rows, 15 columns");
+        assert_eq!(session.execute("find S").unwrap(), "1 found: 3,1");
+        assert_eq!(
+            session.execute("path S E").unwrap(),
+            "84 steps from 3,1 to 7,5"
+        );
+        assert_eq!(session.execute("path S E .#").unwrap(), "no path");
+        assert!(session.execute("path S X").is_err());
+        assert_eq!(
+            session.execute("path 3,1 7,5").unwrap(),
+            "84 steps from 3,1 to 7,5"
+        );
+        assert!(session.execute("path 20,0 E").is_err());
+        assert!(session.execute("path S 3,15").is_err());
+
+        assert!(session
+            .execute("time 1 3")
+            .unwrap()
+            .ends_with("over 3 runs"));
+        assert!(session.execute("example 2").is_err());
+    }
+}
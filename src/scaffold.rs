@@ -0,0 +1,368 @@
+use crate::answers::Answers;
+use crate::common::{default_input_path, DEFAULT_YEAR};
+use crate::solution::Key;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Source of a new day module, with `{year}`, `{day}` and `{name}` to be
+/// filled in.
+const TEMPLATE: &str = include_str!("../templates/day.rs.tmpl");
+
+/// Width the list of days in `register_days!` is wrapped at.
+const LINE_WIDTH: usize = 96;
+
+/// Outputs the source of a new day module from the template.
+#[must_use]
+#[allow(clippy::literal_string_with_formatting_args)]
+pub fn render(key: Key, name: &str) -> String {
+    let name = name.replace('\\', "\\\\").replace('"', "\\\"");
+    TEMPLATE
+        .replace("{year}", &key.year.to_string())
+        .replace("{day}", &key.day.to_string())
+        .replace("{name}", &name)
+}
+
+/// Outputs whether a day module is a placeholder for a day with neither part
+/// solved, consisting of nothing but its `solution!` declaration, which a new
+/// module may replace.
+fn is_stub(source: &str) -> bool {
+    let source = source.trim();
+    source.lines().count() == 1
+        && source.starts_with("crate::solution!")
+        && source.ends_with("unimplemented);")
+}
+
+/// Adds the module `<prefix><n>` to the list given to the macro `name` in
+/// some source, keeping the list ordered, or outputs `None` if it is already
+/// there.
+fn register(source: &str, name: &str, prefix: &str, n: u16) -> Result<Option<String>, String> {
+    let open = format!("{name}! {{");
+    let start = source
+        .find(&open)
+        .ok_or_else(|| format!("could not find the {name}! list"))?
+        + open.len();
+    let end = start
+        + source[start..]
+            .find('}')
+            .ok_or_else(|| format!("unterminated {name}! list"))?;
+
+    let mut numbers: Vec<u16> = source[start..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|module| !module.is_empty())
+        .map(|module| {
+            module
+                .strip_prefix(prefix)
+                .and_then(|n| n.parse().ok())
+                .ok_or_else(|| format!("unexpected module {module:?} in {name}!"))
+        })
+        .collect::<Result<_, _>>()?;
+
+    if numbers.contains(&n) {
+        return Ok(None);
+    }
+    numbers.push(n);
+    numbers.sort_unstable();
+
+    let mut list = String::from("\n");
+    let mut line = String::from("   ");
+    for n in numbers {
+        let module = format!(" {prefix}{n},");
+        if line.len() + module.len() > LINE_WIDTH {
+            list.push_str(&line);
+            list.push('\n');
+            line = String::from("   ");
+        }
+        line.push_str(&module);
+    }
+    list.push_str(&line);
+    list.push('\n');
+
+    Ok(Some(format!(
+        "{}{list}{}",
+        &source[..start],
+        &source[end..]
+    )))
+}
+
+/// Adds `pub mod <module>;` to the module declarations in some source, keeping
+/// them in the order `rustfmt` sorts them in, or declares it before the list
+/// given to the macro `name` if there are none. Outputs `None` if the module is
+/// already declared.
+fn declare(source: &str, name: &str, module: &str) -> Result<Option<String>, String> {
+    let declaration = format!("pub mod {module};");
+    let lines: Vec<&str> = source.lines().collect();
+    if lines.contains(&declaration.as_str()) {
+        return Ok(None);
+    }
+
+    let is_declaration = |line: &str| line.starts_with("pub mod ") && line.ends_with(';');
+    let Some(first) = lines.iter().position(|line| is_declaration(line)) else {
+        let open = format!("{name}! {{");
+        let at = source
+            .find(&open)
+            .ok_or_else(|| format!("could not find the {name}! list"))?;
+        return Ok(Some(format!(
+            "{}{declaration}\n\n{}",
+            &source[..at],
+            &source[at..]
+        )));
+    };
+    let last = first
+        + lines[first..]
+            .iter()
+            .take_while(|line| is_declaration(line))
+            .count();
+
+    let mut declarations = lines[first..last].to_vec();
+    declarations.push(&declaration);
+    declarations.sort_unstable_by_key(|line| line.trim_end_matches(';'));
+
+    let mut output = [&lines[..first], &declarations, &lines[last..]]
+        .concat()
+        .join("\n");
+    output.push('\n');
+    Ok(Some(output))
+}
+
+/// Declares the module `<prefix><n>` in some source and adds it to the list
+/// given to the macro `name`, or outputs `None` if it is already both.
+fn add_module(source: &str, name: &str, prefix: &str, n: u16) -> Result<Option<String>, String> {
+    let declared = declare(source, name, &format!("{prefix}{n}"))?;
+    let registered = register(declared.as_deref().unwrap_or(source), name, prefix, n)?;
+    Ok(registered.or(declared))
+}
+
+/// Appends pending tests of both parts of the day to the source of
+/// `tests/days.rs`, or outputs `None` if it already tests that day.
+fn add_pending_tests(source: &str, key: Key) -> Option<String> {
+    let Key { year, day } = key;
+    if source.contains(&format!("test_day!({year}, {day}, ")) {
+        return None;
+    }
+
+    let mut output = source.trim_end().to_string();
+    for part in [1, 2] {
+        let _ = write!(output, "\ntest_day!({year}, {day}, {part}, pending);");
+    }
+    output.push('\n');
+    Some(output)
+}
+
+/// Appends an entry without any answers for the default input of `key` to the
+/// source of the answers file, or outputs `None` if it already has one.
+fn add_pending_answers(source: &str, key: Key) -> Result<Option<String>, String> {
+    let input = default_input_path(key);
+    if Answers::parse(source)?.get(key, &input).is_some() {
+        return Ok(None);
+    }
+
+    let mut output = source.trim_end().to_string();
+    let _ = write!(output, "\n\n[\"{input}\"]\n");
+    if key.year != DEFAULT_YEAR {
+        let _ = writeln!(output, "year = {}", key.year);
+    }
+    let _ = writeln!(output, "day = {}", key.day);
+    Ok(Some(output))
+}
+
+/// Rewrites a file with the output of `edit`, unless it outputs `None`,
+/// recording the path of the file if it changed.
+fn edit_file(
+    path: PathBuf,
+    changed: &mut Vec<PathBuf>,
+    edit: impl FnOnce(&str) -> Result<Option<String>, String>,
+) -> Result<(), String> {
+    let source = fs::read_to_string(&path)
+        .map_err(|err| format!("could not read {}: {err}", path.display()))?;
+    let edited = edit(&source).map_err(|err| format!("{}: {err}", path.display()))?;
+
+    if let Some(edited) = edited {
+        fs::write(&path, edited)
+            .map_err(|err| format!("could not write {}: {err}", path.display()))?;
+        changed.push(path);
+    }
+    Ok(())
+}
+
+/// Creates the module of a new day in the repository rooted at `root` from the
+/// template, and registers it along with its year if that is new.
+///
+/// Also adds pending tests and answers for the day, leaving anything that is
+/// already in place untouched. An existing module is only replaced if it is a
+/// placeholder declaring both parts unimplemented. Outputs the paths of the
+/// files created or changed.
+///
+/// # Errors
+///
+/// Returns an error if the day is not in the calendar, the module already
+/// exists, or any file cannot be read, parsed, or written.
+pub fn scaffold(root: &Path, key: Key, name: &str) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&key.day) {
+        return Err(format!("day {} is not between 1 and 25", key.day));
+    }
+
+    let year_dir = root.join(format!("src/days/y{}", key.year));
+    let module = year_dir.join(format!("day{}.rs", key.day));
+    if let Ok(existing) = fs::read_to_string(&module) {
+        if !is_stub(&existing) {
+            return Err(format!("{} already exists", module.display()));
+        }
+    }
+
+    let mut changed = Vec::new();
+
+    let year_module = year_dir.with_extension("rs");
+    if !year_module.exists() {
+        fs::write(&year_module, "register_days! {}\n")
+            .map_err(|err| format!("could not write {}: {err}", year_module.display()))?;
+        edit_file(root.join("src/days.rs"), &mut changed, |source| {
+            add_module(source, "register_years", "y", key.year)
+        })?;
+    }
+
+    fs::create_dir_all(&year_dir)
+        .map_err(|err| format!("could not create {}: {err}", year_dir.display()))?;
+    fs::write(&module, render(key, name))
+        .map_err(|err| format!("could not write {}: {err}", module.display()))?;
+    changed.push(module);
+
+    edit_file(year_module, &mut changed, |source| {
+        add_module(source, "register_days", "day", key.day.into())
+    })?;
+    edit_file(root.join("tests/days.rs"), &mut changed, |source| {
+        Ok(add_pending_tests(source, key))
+    })?;
+    edit_file(root.join("answers.toml"), &mut changed, |source| {
+        add_pending_answers(source, key)
+    })?;
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register() {
+        let source = "register_days! {\n    day1, day3,\n}\n";
+        assert_eq!(
+            register(source, "register_days", "day", 2)
+                .unwrap()
+                .unwrap(),
+            "register_days! {\n    day1, day2, day3,\n}\n"
+        );
+        assert_eq!(register(source, "register_days", "day", 3).unwrap(), None);
+        assert!(register(source, "register_years", "y", 2024).is_err());
+        assert!(register("register_days! {\n    foo,\n}\n", "register_days", "day", 1).is_err());
+
+        let all: Vec<String> = (1..=25).map(|day| format!("day{day}")).collect();
+        let registered = (1..=25)
+            .rev()
+            .fold("register_days! {}".to_string(), |source, day| {
+                register(&source, "register_days", "day", day)
+                    .unwrap()
+                    .unwrap()
+            });
+        for line in registered.lines() {
+            assert!(line.len() <= LINE_WIDTH);
+        }
+        let listed: Vec<&str> = registered
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| {
+                word.strip_prefix("day")
+                    .is_some_and(|n| n.parse::<u8>().is_ok())
+            })
+            .collect();
+        assert_eq!(listed, all);
+    }
+
+    #[test]
+    fn test_declare() {
+        let source = "use a;\n\npub mod day1;\npub mod day2;\n\nregister_days! {}\n";
+        assert_eq!(
+            declare(source, "register_days", "day10").unwrap().unwrap(),
+            "use a;\n\npub mod day1;\npub mod day10;\npub mod day2;\n\nregister_days! {}\n"
+        );
+        assert_eq!(declare(source, "register_days", "day2").unwrap(), None);
+        assert_eq!(
+            declare("register_days! {}\n", "register_days", "day7")
+                .unwrap()
+                .unwrap(),
+            "pub mod day7;\n\nregister_days! {}\n"
+        );
+        assert!(declare("", "register_days", "day7").is_err());
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let root = std::env::temp_dir().join("aoc_test_scaffold");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/days/y2024")).unwrap();
+        fs::create_dir_all(root.join("tests")).unwrap();
+        fs::write(
+            root.join("src/days.rs"),
+            "pub mod y2024;\n\nregister_years! {\n    y2024,\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/days/y2024.rs"),
+            "pub mod day1;\npub mod day2;\n\nregister_days! {\n    day1,\n}\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/days/y2024/day1.rs"), "pub fn run1() {}\n").unwrap();
+        fs::write(
+            root.join("src/days/y2024/day2.rs"),
+            "crate::solution!(2024, 2, \"Two\", unimplemented);\n",
+        )
+        .unwrap();
+        fs::write(root.join("tests/days.rs"), "test_day!(2024, 1, 1);\n").unwrap();
+        fs::write(
+            root.join("answers.toml"),
+            "[\"inputs/2024/day01.txt\"]\nday = 1\n",
+        )
+        .unwrap();
+
+        assert!(scaffold(&root, Key::new(2024, 1), "One").is_err());
+        assert!(scaffold(&root, Key::new(2024, 26), "Late").is_err());
+
+        let changed = scaffold(&root, Key::new(2024, 2), "Two \"quoted\"").unwrap();
+        assert_eq!(changed.len(), 4);
+
+        let module = fs::read_to_string(root.join("src/days/y2024/day2.rs")).unwrap();
+        assert!(
+            module.contains(r#"crate::solution!(2024, 2, "Two \"quoted\"", part2 unimplemented);"#)
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/days/y2024.rs")).unwrap(),
+            "pub mod day1;\npub mod day2;\n\nregister_days! {\n    day1, day2,\n}\n"
+        );
+        assert!(fs::read_to_string(root.join("tests/days.rs"))
+            .unwrap()
+            .ends_with("test_day!(2024, 2, 1, pending);\ntest_day!(2024, 2, 2, pending);\n"));
+
+        let changed = scaffold(&root, Key::new(2023, 7), "Seven").unwrap();
+        assert_eq!(changed.len(), 5);
+        assert_eq!(
+            fs::read_to_string(root.join("src/days.rs")).unwrap(),
+            "pub mod y2023;\npub mod y2024;\n\nregister_years! {\n    y2023, y2024,\n}\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/days/y2023.rs")).unwrap(),
+            "pub mod day7;\n\nregister_days! {\n    day7,\n}\n"
+        );
+
+        let answers = Answers::load(root.join("answers.toml")).unwrap();
+        let entry = answers
+            .get(Key::new(2024, 2), "inputs/2024/day02.txt")
+            .unwrap();
+        assert_eq!(entry.expected(1), None);
+        assert!(answers
+            .get(Key::new(2023, 7), "inputs/2023/day07.txt")
+            .is_some());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,539 @@
+use crate::progress::Progress;
+use crate::utils::LinesIterator;
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Identifies a puzzle by the year and day it was released.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Key {
+    pub year: u16,
+    pub day: u8,
+}
+
+impl Key {
+    #[must_use]
+    pub const fn new(year: u16, day: u8) -> Self {
+        Self { year, day }
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+/// Reasons a solver can fail to produce an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The part has not been solved yet.
+    Unimplemented,
+    /// The solver ran, but could not find an answer for the given input.
+    Failed(String),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unimplemented => write!(f, "not implemented"),
+            Self::Failed(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+pub type Answer = Result<String, SolveError>;
+
+/// Conversion from whatever a day's `run1`/`run2` outputs into an [`Answer`],
+/// so that infallible solvers can keep returning a plain `String`.
+pub trait IntoAnswer {
+    /// # Errors
+    ///
+    /// Returns an error if the solver did not produce an answer.
+    fn into_answer(self) -> Answer;
+}
+
+impl IntoAnswer for String {
+    fn into_answer(self) -> Answer {
+        Ok(self)
+    }
+}
+
+impl IntoAnswer for Answer {
+    fn into_answer(self) -> Answer {
+        self
+    }
+}
+
+/// A runtime parameter of a day, such as the size of its grid, which the
+/// worked examples often set differently from the real input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    /// The value used unless another is given.
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+/// What a solver is given along with its input.
+#[derive(Default)]
+pub struct Context {
+    /// Where a long-running solver reports how far along it is, instead of
+    /// printing anything itself.
+    pub progress: Progress,
+    /// The parameters of the day being solved.
+    pub params: &'static [Param],
+    /// Values given for some of those parameters, overriding their defaults.
+    pub values: HashMap<String, String>,
+}
+
+impl Context {
+    /// Outputs a context in which the given parameters take their default
+    /// values, and progress is not reported.
+    #[must_use]
+    pub fn new(params: &'static [Param]) -> Self {
+        Self {
+            params,
+            ..Self::default()
+        }
+    }
+
+    /// Outputs the same context with the given values of its parameters, by
+    /// name, later ones taking precedence.
+    #[must_use]
+    pub fn with_values(mut self, values: &[(String, String)]) -> Self {
+        self.values.extend(values.iter().cloned());
+        self
+    }
+
+    /// Outputs the value of the parameter `name`, parsed as a `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the day has no parameter `name`, or its value
+    /// cannot be parsed as a `T`.
+    pub fn param<T: FromStr>(&self, name: &str) -> Result<T, SolveError> {
+        let param = self
+            .params
+            .iter()
+            .find(|param| param.name == name)
+            .ok_or_else(|| SolveError::Failed(format!("no parameter {name}")))?;
+
+        let value = self.values.get(name).map_or(param.default, String::as_str);
+        value.parse().map_err(|_| {
+            SolveError::Failed(format!("invalid value {value:?} for parameter {name}"))
+        })
+    }
+}
+
+/// A function solving a part of a day, from the parsed input alone or along
+/// with the [`Context`] it runs in.
+///
+/// The marker `M` only tells the two kinds of function apart, so that either
+/// can be given to [`solution!`](crate::solution!) as is.
+pub trait PartSolver<I, M> {
+    /// # Errors
+    ///
+    /// Returns an error if the solver does not produce an answer.
+    fn solve_part(&self, input: &I, context: &Context) -> Answer;
+}
+
+/// Marks a [`PartSolver`] taking the input alone.
+pub struct InputOnly;
+
+/// Marks a [`PartSolver`] taking the input and the context.
+pub struct WithContext;
+
+impl<I, R: IntoAnswer, F: Fn(&I) -> R> PartSolver<I, (InputOnly, R)> for F {
+    fn solve_part(&self, input: &I, _: &Context) -> Answer {
+        self(input).into_answer()
+    }
+}
+
+impl<I, R: IntoAnswer, F: Fn(&I, &Context) -> R> PartSolver<I, (WithContext, R)> for F {
+    fn solve_part(&self, input: &I, context: &Context) -> Answer {
+        self(input, context).into_answer()
+    }
+}
+
+/// One of a puzzle's worked examples, along with the answers given for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+    /// Values of the day's parameters (see [`Param`]) which differ for this
+    /// example, by name.
+    pub params: &'static [(&'static str, &'static str)],
+}
+
+impl Example {
+    #[must_use]
+    pub const fn expected(&self, part: u8) -> Option<&'static str> {
+        match part {
+            1 => self.part1,
+            2 => self.part2,
+            _ => None,
+        }
+    }
+}
+
+/// A day's input after parsing, in whatever form the day's solution chose.
+pub type ParsedInput = Box<dyn Any + Send>;
+
+/// Outputs the parsed input of a day as the type its solution parses it into.
+///
+/// # Errors
+///
+/// Returns an error if the input was parsed by another day's solution.
+pub fn downcast<T: 'static>(input: &dyn Any) -> Result<&T, SolveError> {
+    input
+        .downcast_ref()
+        .ok_or_else(|| SolveError::Failed("input was parsed by another solution".to_string()))
+}
+
+/// A solution to both parts of a single day's puzzle.
+///
+/// The input is parsed once by [`Solution::parse`], after which either part
+/// can be solved from it any number of times.
+pub trait Solution: Sync {
+    /// The title of the puzzle.
+    fn name(&self) -> &'static str;
+
+    fn day(&self) -> u8;
+
+    fn year(&self) -> u16;
+
+    /// Reads the input into the form both parts are solved from.
+    fn parse(&self, lines: &mut LinesIterator) -> ParsedInput;
+
+    /// # Errors
+    ///
+    /// Returns an error if part 1 is unimplemented or fails on the input.
+    fn part1(&self, input: &dyn Any, context: &Context) -> Answer;
+
+    /// # Errors
+    ///
+    /// Returns an error if part 2 is unimplemented or fails on the input.
+    fn part2(&self, input: &dyn Any, context: &Context) -> Answer;
+
+    /// Outputs whether the given part has been solved, so that callers can
+    /// skip it without reading any input.
+    fn is_implemented(&self, part: u8) -> bool {
+        matches!(part, 1 | 2)
+    }
+
+    /// The worked examples from the puzzle's description.
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    /// The runtime parameters the solvers read from their [`Context`].
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+
+    fn key(&self) -> Key {
+        Key::new(self.year(), self.day())
+    }
+
+    /// Solves the given part from an input parsed by [`Solution::parse`], or
+    /// outputs `None` if `part` is neither 1 nor 2.
+    fn solve(&self, part: u8, input: &dyn Any, context: &Context) -> Option<Answer> {
+        match part {
+            1 => Some(self.part1(input, context)),
+            2 => Some(self.part2(input, context)),
+            _ => None,
+        }
+    }
+
+    /// Parses the input and runs the given part on it, or outputs `None` if
+    /// `part` is neither 1 nor 2.
+    fn run(&self, part: u8, lines: &mut LinesIterator, context: &Context) -> Option<Answer> {
+        if !matches!(part, 1 | 2) {
+            return None;
+        }
+        self.solve(part, &*self.parse(lines), context)
+    }
+}
+
+/// Collection of solutions, ordered by their key.
+#[derive(Default)]
+pub struct Registry {
+    solutions: BTreeMap<Key, &'static dyn Solution>,
+}
+
+impl Registry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            solutions: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a solution to the registry, outputting the solution previously
+    /// registered under the same key, if any.
+    pub fn register(&mut self, solution: &'static dyn Solution) -> Option<&'static dyn Solution> {
+        self.solutions.insert(solution.key(), solution)
+    }
+
+    #[must_use]
+    pub fn get(&self, key: Key) -> Option<&'static dyn Solution> {
+        self.solutions.get(&key).copied()
+    }
+
+    #[must_use]
+    pub fn contains(&self, key: Key) -> bool {
+        self.solutions.contains_key(&key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = Key> + '_ {
+        self.solutions.keys().copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'static dyn Solution> + '_ {
+        self.solutions.values().copied()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.solutions.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.solutions.is_empty()
+    }
+}
+
+/// Stand-in for a part that has not been solved yet.
+///
+/// # Errors
+///
+/// Always returns [`SolveError::Unimplemented`].
+pub const fn unimplemented<T>(_input: &T) -> Answer {
+    Err(SolveError::Unimplemented)
+}
+
+/// Stand-in parser for a day with neither part solved, which ignores the
+/// input.
+pub const fn skip_input(_lines: &mut LinesIterator) {}
+
+/// Declares a unit struct `Solver` implementing [`Solution`] for the day
+/// module it is invoked in.
+///
+/// By default the input is read by the module's `parse` function into its
+/// type `Input`, from which each part is solved by the module's `run1` and
+/// `run2` functions, taking an `&Input` and optionally a `&Context`, and
+/// outputting either a `String` or an [`Answer`]. The module's worked examples
+/// are read from a constant `EXAMPLES: &[Example]`. Days with runtime
+/// parameters are declared with a trailing `params`, and read them from a
+/// constant `PARAMS: &[Param]`.
+///
+/// Days that are not fully solved yet can instead be declared with a trailing
+/// `part2 unimplemented` or `unimplemented`, in which case the missing parts
+/// need no function at all, and a day with neither part solved needs no
+/// parser or examples either.
+#[macro_export]
+macro_rules! solution {
+    ($year:literal, $day:literal, $name:literal) => {
+        $crate::solution!(
+            @solver $year, $day, $name,
+            parse, Input,
+            run1, true,
+            run2, true,
+            EXAMPLES,
+            &[]
+        );
+    };
+    ($year:literal, $day:literal, $name:literal, params) => {
+        $crate::solution!(
+            @solver $year, $day, $name,
+            parse, Input,
+            run1, true,
+            run2, true,
+            EXAMPLES,
+            PARAMS
+        );
+    };
+    ($year:literal, $day:literal, $name:literal, part2 unimplemented) => {
+        $crate::solution!(
+            @solver $year, $day, $name,
+            parse, Input,
+            run1, true,
+            $crate::solution::unimplemented::<Input>, false,
+            EXAMPLES,
+            &[]
+        );
+    };
+    ($year:literal, $day:literal, $name:literal, unimplemented) => {
+        $crate::solution!(
+            @solver $year, $day, $name,
+            $crate::solution::skip_input, (),
+            $crate::solution::unimplemented::<()>, false,
+            $crate::solution::unimplemented::<()>, false,
+            &[],
+            &[]
+        );
+    };
+    (@solver $year:literal, $day:literal, $name:literal,
+        $parse:path, $input:ty,
+        $run1:path, $implemented1:literal,
+        $run2:path, $implemented2:literal,
+        $examples:expr, $params:expr) => {
+        pub struct Solver;
+
+        impl $crate::solution::Solution for Solver {
+            fn name(&self) -> &'static str {
+                $name
+            }
+
+            fn day(&self) -> u8 {
+                $day
+            }
+
+            fn year(&self) -> u16 {
+                $year
+            }
+
+            fn parse(
+                &self,
+                lines: &mut $crate::utils::LinesIterator,
+            ) -> $crate::solution::ParsedInput {
+                Box::new($parse(lines))
+            }
+
+            fn part1(
+                &self,
+                input: &dyn ::std::any::Any,
+                context: &$crate::solution::Context,
+            ) -> $crate::solution::Answer {
+                $crate::solution::downcast::<$input>(input).and_then(|input| {
+                    $crate::solution::PartSolver::solve_part(&$run1, input, context)
+                })
+            }
+
+            fn part2(
+                &self,
+                input: &dyn ::std::any::Any,
+                context: &$crate::solution::Context,
+            ) -> $crate::solution::Answer {
+                $crate::solution::downcast::<$input>(input).and_then(|input| {
+                    $crate::solution::PartSolver::solve_part(&$run2, input, context)
+                })
+            }
+
+            fn is_implemented(&self, part: u8) -> bool {
+                match part {
+                    1 => $implemented1,
+                    2 => $implemented2,
+                    _ => false,
+                }
+            }
+
+            fn examples(&self) -> &'static [$crate::solution::Example] {
+                $examples
+            }
+
+            fn params(&self) -> &'static [$crate::solution::Param] {
+                $params
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Dummy(u16, u8);
+
+    impl Solution for Dummy {
+        fn name(&self) -> &'static str {
+            "Dummy"
+        }
+
+        fn day(&self) -> u8 {
+            self.1
+        }
+
+        fn year(&self) -> u16 {
+            self.0
+        }
+
+        fn parse(&self, lines: &mut LinesIterator) -> ParsedInput {
+            Box::new(lines.count())
+        }
+
+        fn part1(&self, input: &dyn Any, _: &Context) -> Answer {
+            downcast::<usize>(input).map(|count| format!("{}", count + usize::from(self.1)))
+        }
+
+        fn part2(&self, _: &dyn Any, _: &Context) -> Answer {
+            Err(SolveError::Unimplemented)
+        }
+    }
+
+    static LATE: Dummy = Dummy(2024, 7);
+    static EARLY: Dummy = Dummy(2023, 25);
+    static DUPLICATE: Dummy = Dummy(2024, 7);
+
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry::new();
+        assert!(registry.register(&LATE).is_none());
+        assert!(registry.register(&EARLY).is_none());
+        assert!(registry.register(&DUPLICATE).is_some());
+
+        assert_eq!(registry.len(), 2);
+        assert_eq!(
+            registry.keys().collect::<Vec<_>>(),
+            vec![Key::new(2023, 25), Key::new(2024, 7)]
+        );
+        assert!(registry.contains(Key::new(2024, 7)));
+        assert!(registry.get(Key::new(2024, 8)).is_none());
+        assert_eq!(registry.get(Key::new(2023, 25)).unwrap().name(), "Dummy");
+    }
+
+    #[test]
+    fn test_parse_and_solve() {
+        let input = LATE.parse(&mut crate::utils::lines_from_str("a\nb\n"));
+        let context = Context::default();
+        assert_eq!(LATE.solve(1, &*input, &context), Some(Ok("9".to_string())));
+        assert_eq!(LATE.solve(1, &*input, &context), Some(Ok("9".to_string())));
+        assert_eq!(
+            LATE.solve(2, &*input, &context),
+            Some(Err(SolveError::Unimplemented))
+        );
+        assert_eq!(LATE.solve(3, &*input, &context), None);
+
+        assert!(matches!(
+            LATE.solve(1, &"not a count", &context),
+            Some(Err(SolveError::Failed(_)))
+        ));
+    }
+
+    #[test]
+    fn test_param() {
+        const PARAMS: &[Param] = &[Param {
+            name: "size",
+            default: "71",
+            description: "",
+        }];
+
+        let mut context = Context::new(PARAMS);
+        assert_eq!(context.param::<usize>("size"), Ok(71));
+        assert!(context.param::<usize>("width").is_err());
+
+        context.values.insert("size".to_string(), "7".to_string());
+        assert_eq!(context.param::<usize>("size"), Ok(7));
+
+        context
+            .values
+            .insert("size".to_string(), "seven".to_string());
+        assert!(context.param::<usize>("size").is_err());
+    }
+}
@@ -0,0 +1,591 @@
+use crate::fetch::{self, HttpClient, BASE_URL};
+use crate::solution::Key;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Name of the file in the cache directory every submission is recorded in.
+pub const HISTORY_FILE: &str = "submissions.tsv";
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without saying in which direction.
+    Wrong,
+    /// Not checked, because the last answer was submitted too recently.
+    TooSoon,
+    /// Not checked, because the part is already solved or not unlocked yet.
+    WrongLevel,
+    /// The response was not understood.
+    Unrecognized,
+}
+
+impl Verdict {
+    /// Outputs whether the answer was checked and found to be wrong.
+    #[must_use]
+    pub const fn is_wrong(self) -> bool {
+        matches!(self, Self::TooHigh | Self::TooLow | Self::Wrong)
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Correct => "correct",
+            Self::TooHigh => "too-high",
+            Self::TooLow => "too-low",
+            Self::Wrong => "wrong",
+            Self::TooSoon => "too-soon",
+            Self::WrongLevel => "wrong-level",
+            Self::Unrecognized => "unrecognized",
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Self::Correct,
+            Self::TooHigh,
+            Self::TooLow,
+            Self::Wrong,
+            Self::TooSoon,
+            Self::WrongLevel,
+            Self::Unrecognized,
+        ]
+        .into_iter()
+        .find(|verdict| verdict.as_str() == s)
+        .ok_or_else(|| format!("unknown verdict {s:?}"))
+    }
+}
+
+/// Outputs the main text of a page, which the site puts in an `<article>`,
+/// with any tags removed.
+fn article_text(body: &str) -> String {
+    let start = body.find("<article").unwrap_or(0);
+    let end = body[start..]
+        .find("</article>")
+        .map_or(body.len(), |end| start + end);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in body[start..end].chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses how long the site asks to wait before the next submission, from
+/// either "You have 1m 30s left to wait" or "Please wait 5 minutes".
+fn parse_wait(text: &str) -> Option<u64> {
+    if let Some(start) = text.find("You have ") {
+        let rest = &text[start + "You have ".len()..];
+        let end = rest.find(" left to wait")?;
+        return rest[..end].split_whitespace().try_fold(0, |total, part| {
+            let (n, unit) = part.split_at(part.len().checked_sub(1)?);
+            let n: u64 = n.parse().ok()?;
+            let secs = match unit {
+                "h" => 3600,
+                "m" => 60,
+                "s" => 1,
+                _ => return None,
+            };
+            Some(total + n * secs)
+        });
+    }
+
+    let start = text.to_lowercase().find("please wait ")?;
+    let mut words = text[start + "please wait ".len()..].split_whitespace();
+    let n = match words.next()? {
+        "one" => 1,
+        n => n.parse().ok()?,
+    };
+    let secs = match words.next()?.trim_end_matches(['.', ',']) {
+        "second" | "seconds" => 1,
+        "minute" | "minutes" => 60,
+        "hour" | "hours" => 3600,
+        _ => return None,
+    };
+    Some(n * secs)
+}
+
+/// Parses the page the site responds to a submission with into its verdict,
+/// along with how many seconds it asks to wait before submitting again.
+#[must_use]
+pub fn parse_response(body: &str) -> (Verdict, Option<u64>) {
+    let text = article_text(body);
+
+    let verdict = if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("your answer is too high") {
+        Verdict::TooHigh
+    } else if text.contains("your answer is too low") {
+        Verdict::TooLow
+    } else if text.contains("That's not the right answer") {
+        Verdict::Wrong
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::TooSoon
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unrecognized
+    };
+
+    (verdict, parse_wait(&text))
+}
+
+/// A single submitted answer and the site's response to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    /// When the answer was submitted, in seconds since the Unix epoch.
+    pub time: u64,
+    pub key: Key,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// How many seconds the site asked to wait before submitting again.
+    pub wait: Option<u64>,
+}
+
+impl Submission {
+    /// Formats the submission as a line of the history file, without a
+    /// newline.
+    fn to_line(&self) -> String {
+        let wait = self
+            .wait
+            .map_or_else(|| "-".to_string(), |wait| wait.to_string());
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{wait}\t{}",
+            self.time, self.key.year, self.key.day, self.part, self.verdict, self.answer
+        )
+    }
+
+    fn from_line(line: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = line.splitn(7, '\t').collect();
+        let [time, year, day, part, verdict, wait, answer] = fields[..] else {
+            return Err("expected 7 tab-separated fields".to_string());
+        };
+        let number = |field: &str, name: &str| {
+            field
+                .parse::<u64>()
+                .map_err(|_| format!("invalid {name} {field:?}"))
+        };
+
+        Ok(Self {
+            time: number(time, "time")?,
+            key: Key::new(
+                year.parse().map_err(|_| format!("invalid year {year:?}"))?,
+                day.parse().map_err(|_| format!("invalid day {day:?}"))?,
+            ),
+            part: part.parse().map_err(|_| format!("invalid part {part:?}"))?,
+            answer: answer.to_string(),
+            verdict: verdict.parse()?,
+            wait: if wait == "-" {
+                None
+            } else {
+                Some(number(wait, "wait")?)
+            },
+        })
+    }
+}
+
+/// Every answer submitted so far, kept as one tab-separated line per
+/// submission: time, year, day, part, verdict, wait (or `-`), and answer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct History {
+    submissions: Vec<Submission>,
+}
+
+impl History {
+    /// # Errors
+    ///
+    /// Returns an error, naming the line, if any line is malformed.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let submissions = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|(i, line)| {
+                Submission::from_line(line).map_err(|err| format!("line {}: {err}", i + 1))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { submissions })
+    }
+
+    /// Reads the history from a file, which is treated as empty if it does
+    /// not exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(path)
+            .map_err(|err| format!("could not read {}: {err}", path.display()))?;
+        Self::parse(&text).map_err(|err| format!("{}: {err}", path.display()))
+    }
+
+    /// Adds a submission, appending it to the file at `path` too.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn record(&mut self, path: &Path, submission: Submission) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|err| format!("could not create {}: {err}", dir.display()))?;
+        }
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{}", submission.to_line()))
+            .map_err(|err| format!("could not write {}: {err}", path.display()))?;
+
+        self.submissions.push(submission);
+        Ok(())
+    }
+
+    pub fn submissions(&self) -> impl Iterator<Item = &Submission> {
+        self.submissions.iter()
+    }
+
+    /// Outputs the bounds the answer to a part is known to lie strictly
+    /// between, from the numeric answers found to be too low or too high.
+    #[must_use]
+    pub fn bounds(&self, key: Key, part: u8) -> (Option<i128>, Option<i128>) {
+        let mut lower = None;
+        let mut upper = None;
+        for submission in self.for_part(key, part) {
+            let Ok(answer) = submission.answer.parse::<i128>() else {
+                continue;
+            };
+            match submission.verdict {
+                Verdict::TooLow => lower = lower.max(Some(answer)),
+                Verdict::TooHigh => {
+                    upper = Some(upper.map_or(answer, |upper: i128| upper.min(answer)));
+                }
+                _ => {}
+            }
+        }
+        (lower, upper)
+    }
+
+    fn for_part(&self, key: Key, part: u8) -> impl Iterator<Item = &Submission> {
+        self.submissions
+            .iter()
+            .filter(move |submission| submission.key == key && submission.part == part)
+    }
+
+    /// Checks whether an answer is worth submitting at time `now`, outputting
+    /// the reason not to otherwise: the answer is empty or spans several
+    /// fields or lines of the history, the part is already solved, the answer
+    /// is known to be wrong or lies outside the known bounds, or the site
+    /// asked to wait until later.
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason not to submit the answer.
+    pub fn check(&self, key: Key, part: u8, answer: &str, now: u64) -> Result<(), String> {
+        if answer.trim().is_empty() {
+            return Err("the answer is empty".to_string());
+        }
+        if answer.contains(['\t', '\n', '\r']) {
+            return Err(format!("{answer:?} contains a tab or line break"));
+        }
+
+        for submission in self.for_part(key, part) {
+            if submission.verdict == Verdict::Correct {
+                return Err(format!(
+                    "part {part} of {key} was already solved with {}",
+                    submission.answer
+                ));
+            }
+            if submission.verdict.is_wrong() && submission.answer == answer {
+                return Err(format!(
+                    "{answer} was already rejected ({})",
+                    submission.verdict
+                ));
+            }
+        }
+
+        if let Ok(n) = answer.parse::<i128>() {
+            match self.bounds(key, part) {
+                (Some(lower), _) if n <= lower => {
+                    return Err(format!("{answer} is too low: {lower} already was"));
+                }
+                (_, Some(upper)) if n >= upper => {
+                    return Err(format!("{answer} is too high: {upper} already was"));
+                }
+                _ => {}
+            }
+        }
+
+        let ready = self
+            .submissions
+            .iter()
+            .filter_map(|submission| submission.wait.map(|wait| submission.time + wait))
+            .max();
+        if let Some(ready) = ready.filter(|&ready| ready > now) {
+            return Err(format!(
+                "the site asked to wait another {}s before submitting",
+                ready - now
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Submits answers to the site, keeping track of every response.
+pub struct Submitter<'a> {
+    pub client: &'a dyn HttpClient,
+    pub base_url: String,
+    /// The session token, which is needed to submit anything.
+    pub session: Option<String>,
+    /// Where the history of submissions is kept.
+    pub history_path: PathBuf,
+}
+
+impl<'a> Submitter<'a> {
+    /// Outputs a submitter posting to the real site with the same session
+    /// token as [`fetch::Fetcher::from_env`], keeping its history in the
+    /// per-user cache directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no cache directory.
+    pub fn from_env(client: &'a dyn HttpClient) -> Result<Self, String> {
+        let cache_dir = fetch::require_cache_dir()?;
+        Ok(Self {
+            client,
+            base_url: BASE_URL.to_string(),
+            session: fetch::session_from_env(&cache_dir),
+            history_path: cache_dir.join(HISTORY_FILE),
+        })
+    }
+
+    /// Submits an answer to a part, unless the history shows that doing so
+    /// would be pointless, recording the site's response in the history.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the answer is not worth submitting (see
+    /// [`History::check`]), there is no session token, the request fails, or
+    /// the history cannot be read or written.
+    pub fn submit(&self, key: Key, part: u8, answer: &str) -> Result<Submission, String> {
+        let mut history = History::load(&self.history_path)?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        history.check(key, part, answer, now)?;
+
+        let session = self.session.as_deref().ok_or_else(|| {
+            fetch::missing_session(self.history_path.parent().unwrap_or_else(|| Path::new(".")))
+        })?;
+        let url = format!(
+            "{}/{}/day/{}/answer",
+            self.base_url.trim_end_matches('/'),
+            key.year,
+            key.day
+        );
+        let level = part.to_string();
+        let body = self
+            .client
+            .post(&url, session, &[("level", &level), ("answer", answer)])?;
+
+        let (verdict, wait) = parse_response(&body);
+        let submission = Submission {
+            time: now,
+            key,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            wait,
+        };
+        history.record(&self.history_path, submission.clone())?;
+        Ok(submission)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    fn page(message: &str) -> String {
+        format!("<html><main>\n<article><p>{message}</p></article>\n</main></html>")
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let cases = [
+            (
+                "That's the right answer!  You are <em>one gold star</em> closer.",
+                Verdict::Correct,
+                None,
+            ),
+            (
+                "That's not the right answer; your answer is too high.  Please wait one minute \
+                 before trying again. <a href=\"/2024/day/1\">[Return to Day 1]</a>",
+                Verdict::TooHigh,
+                Some(60),
+            ),
+            (
+                "That's not the right answer; your answer is too low.  please wait 5 minutes \
+                 before trying again.",
+                Verdict::TooLow,
+                Some(300),
+            ),
+            (
+                "That's not the right answer.  If you're stuck, make sure you're using the full \
+                 input data.",
+                Verdict::Wrong,
+                None,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 1m 30s left to wait.",
+                Verdict::TooSoon,
+                Some(90),
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Verdict::WrongLevel,
+                None,
+            ),
+            ("Something else entirely", Verdict::Unrecognized, None),
+        ];
+
+        for (message, verdict, wait) in cases {
+            assert_eq!(parse_response(&page(message)), (verdict, wait), "{message}");
+        }
+    }
+
+    fn submission(time: u64, answer: &str, verdict: Verdict, wait: Option<u64>) -> Submission {
+        Submission {
+            time,
+            key: Key::new(2024, 1),
+            part: 1,
+            answer: answer.to_string(),
+            verdict,
+            wait,
+        }
+    }
+
+    #[test]
+    fn test_history() {
+        let key = Key::new(2024, 1);
+        let history = History {
+            submissions: vec![
+                submission(100, "50", Verdict::TooLow, Some(60)),
+                submission(200, "90", Verdict::TooHigh, Some(60)),
+                submission(300, "70", Verdict::Wrong, Some(300)),
+                submission(400, "1,2", Verdict::Wrong, None),
+            ],
+        };
+
+        let text: String = history
+            .submissions()
+            .map(|submission| submission.to_line() + "\n")
+            .collect();
+        assert_eq!(History::parse(&text).unwrap(), history);
+        assert!(History::parse("1\t2024\t1\t1\tmaybe\t-\t5").is_err());
+
+        assert_eq!(history.bounds(key, 1), (Some(50), Some(90)));
+        assert!(history.check(key, 1, "60", 600).is_ok());
+        assert!(history.check(key, 1, "60", 599).is_err());
+        assert!(history.check(key, 1, "70", 600).is_err());
+        assert!(history.check(key, 1, "1,2", 600).is_err());
+        assert!(history.check(key, 1, "50", 600).is_err());
+        assert!(history.check(key, 1, "95", 600).is_err());
+        assert!(history.check(key, 1, "", 600).is_err());
+        assert!(history.check(key, 2, "9\t5", 600).is_err());
+        assert!(history.check(key, 2, "95\n", 600).is_err());
+        assert!(history.check(key, 2, "95", 600).is_ok());
+
+        let mut solved = history;
+        solved
+            .submissions
+            .push(submission(700, "60", Verdict::Correct, None));
+        assert!(solved.check(key, 1, "61", 800).is_err());
+    }
+
+    /// Answers every submission with the next of some canned pages, keeping
+    /// the forms it was sent.
+    struct StubClient {
+        responses: RefCell<Vec<String>>,
+        forms: RefCell<Vec<String>>,
+    }
+
+    impl HttpClient for StubClient {
+        fn get(&self, _: &str, _: &str) -> Result<String, String> {
+            Err("unexpected GET".to_string())
+        }
+
+        fn post(&self, url: &str, _: &str, form: &[(&str, &str)]) -> Result<String, String> {
+            let form: Vec<String> = form.iter().map(|(k, v)| format!("{k}={v}")).collect();
+            self.forms
+                .borrow_mut()
+                .push(format!("{url} {}", form.join("&")));
+            Ok(self.responses.borrow_mut().remove(0))
+        }
+    }
+
+    #[test]
+    fn test_submit() {
+        let dir = std::env::temp_dir().join("aoc_test_submit");
+        let _ = fs::remove_dir_all(&dir);
+
+        let client = StubClient {
+            responses: RefCell::new(vec![page(
+                "That's not the right answer; your answer is too low.",
+            )]),
+            forms: RefCell::new(Vec::new()),
+        };
+        let submitter = Submitter {
+            client: &client,
+            base_url: "http://stub".to_string(),
+            session: Some("abc".to_string()),
+            history_path: dir.join(HISTORY_FILE),
+        };
+
+        let key = Key::new(2024, 3);
+        let submission = submitter.submit(key, 2, "12").unwrap();
+        assert_eq!(submission.verdict, Verdict::TooLow);
+        assert_eq!(
+            client.forms.borrow().as_slice(),
+            ["http://stub/2024/day/3/answer level=2&answer=12"]
+        );
+
+        // Known to be too low, so never sent.
+        assert!(submitter.submit(key, 2, "11").is_err());
+        assert!(submitter.submit(key, 2, "12").is_err());
+        assert_eq!(client.forms.borrow().len(), 1);
+
+        let history = History::load(dir.join(HISTORY_FILE)).unwrap();
+        assert_eq!(history.submissions().count(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
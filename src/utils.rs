@@ -1,4 +1,20 @@
-pub type LinesIterator = std::io::Lines<std::io::BufReader<std::fs::File>>;
+use std::io::{BufRead, Cursor};
+
+/// Lines of a puzzle input, read from any source: a file, stdin, or a string.
+pub type LinesIterator = std::io::Lines<Box<dyn BufRead + Send>>;
+
+/// Outputs the lines read from the given reader.
+pub fn lines_from_reader<R: BufRead + Send + 'static>(reader: R) -> LinesIterator {
+    let reader: Box<dyn BufRead + Send> = Box::new(reader);
+    reader.lines()
+}
+
+/// Outputs the lines of the given string, for running solvers on input held in
+/// memory.
+#[must_use]
+pub fn lines_from_str(input: &str) -> LinesIterator {
+    lines_from_reader(Cursor::new(input.to_string()))
+}
 
 /// Takes an iterator to a bunch of strings and a separator, returns an iterator over vectors of strings, where each vector is obtained by splitting each line in the iterator by the separator.
 pub fn lines_to_grid<'a>(
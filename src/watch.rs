@@ -0,0 +1,255 @@
+use crate::answers::{verify_examples, Status, Verification};
+use crate::common::{
+    find_solution, format_duration, run_timed, RunError, RunOptions, RunReport, STDIN_PATH,
+};
+use crate::solution::Key;
+use std::fmt::Write;
+use std::fs;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchOptions {
+    /// How often the input is checked for changes.
+    pub interval: Duration,
+    /// Whether the day's worked examples are run along with the input.
+    pub examples: bool,
+    /// Limits on every run.
+    pub run: RunOptions,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(500),
+            examples: false,
+            run: RunOptions::default(),
+        }
+    }
+}
+
+/// What identifies a version of the input: its modification time and length,
+/// or `None` if it does not exist.
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &str) -> Stamp {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Outputs the answer of a report, or a description of why there is none.
+fn answer(report: &RunReport) -> String {
+    report
+        .result
+        .as_ref()
+        .map_or_else(|_| report.status(), Clone::clone)
+}
+
+/// Formats a duration along with how it compares to an earlier one, if any.
+fn format_change(current: Duration, previous: Option<Duration>) -> String {
+    let current_text = format_duration(current);
+    match previous {
+        Some(previous) if current >= previous => {
+            format!(
+                "{current_text} (+{})",
+                format_duration(current.saturating_sub(previous))
+            )
+        }
+        Some(previous) => format!(
+            "{current_text} (-{})",
+            format_duration(previous.saturating_sub(current))
+        ),
+        None => current_text,
+    }
+}
+
+/// Describes a run of a part, comparing its answer and timings to those of
+/// the previous run, if any.
+#[must_use]
+pub fn describe_run(run: usize, previous: Option<&RunReport>, report: &RunReport) -> String {
+    let current = answer(report);
+    let change = match previous.map(answer) {
+        None => String::new(),
+        Some(previous) if previous == current => " (unchanged)".to_string(),
+        Some(previous) => format!(" (was {previous})"),
+    };
+
+    let mut output = format!("Run {run}: {current}{change}");
+    if report.result.is_ok() {
+        if let Some(parse) = report.parse_elapsed {
+            let previous = previous.and_then(|previous| previous.parse_elapsed);
+            let _ = write!(output, "  parse {}", format_change(parse, previous));
+        }
+        let previous = previous
+            .filter(|previous| previous.result.is_ok())
+            .map(|previous| previous.elapsed);
+        let _ = write!(
+            output,
+            "  solve {}",
+            format_change(report.elapsed, previous)
+        );
+    }
+    output
+}
+
+/// Describes the outcome of running the examples, one line each.
+fn describe_examples(verifications: &[Verification]) -> String {
+    let mut output = String::new();
+    for verification in verifications {
+        let report = &verification.report;
+        let _ = match verification.status() {
+            Status::Pass => writeln!(output, "  {}: pass", report.path),
+            _ => writeln!(
+                output,
+                "  {}: fail, expected {}, got {}",
+                report.path,
+                verification.expected.as_deref().unwrap_or("-"),
+                answer(report)
+            ),
+        };
+    }
+    output
+}
+
+/// Re-runs a part of a day whenever its input changes.
+pub struct Watcher {
+    key: Key,
+    part: u8,
+    path: String,
+    options: WatchOptions,
+    /// The version of the input last run on, or `None` before the first run.
+    stamp: Option<Stamp>,
+    previous: Option<RunReport>,
+    runs: usize,
+}
+
+impl Watcher {
+    /// # Errors
+    ///
+    /// Returns an error if the part cannot be run, or the input is stdin,
+    /// which cannot be watched.
+    pub fn new(key: Key, part: u8, path: &str, options: WatchOptions) -> Result<Self, RunError> {
+        find_solution(key, part)?;
+        if path == STDIN_PATH {
+            return Err(RunError::BadArgument("cannot watch stdin".to_string()));
+        }
+
+        Ok(Self {
+            key,
+            part,
+            path: path.to_string(),
+            options,
+            stamp: None,
+            previous: None,
+            runs: 0,
+        })
+    }
+
+    /// Runs the part if this is the first poll or the input changed since
+    /// the last run, outputting a description of the run, or `None` if
+    /// nothing changed.
+    pub fn poll(&mut self) -> Option<String> {
+        let stamp = stamp(&self.path);
+        if self.stamp == Some(stamp) {
+            return None;
+        }
+        self.stamp = Some(stamp);
+        self.runs += 1;
+
+        let report = run_timed(self.key, self.part, &self.path, &self.options.run);
+        let mut output = describe_run(self.runs, self.previous.as_ref(), &report);
+        output.push('\n');
+
+        if self.options.examples {
+            match verify_examples(self.key, self.part, &self.options.run) {
+                Ok(verifications) => output.push_str(&describe_examples(&verifications)),
+                Err(err) => {
+                    let _ = writeln!(output, "  examples: {err}");
+                }
+            }
+        }
+
+        self.previous = Some(report);
+        Some(output)
+    }
+}
+
+/// Runs the part on its input, and again every time the input changes,
+/// printing how the answer and timings compare to the previous run. Never
+/// returns unless the part cannot be watched.
+///
+/// # Errors
+///
+/// Returns an error if the part cannot be run, or the input is stdin.
+pub fn watch(key: Key, part: u8, path: &str, options: WatchOptions) -> Result<(), RunError> {
+    let interval = options.interval;
+    let mut watcher = Watcher::new(key, part, path, options)?;
+    eprintln!("Watching {path} for changes to re-run part {part} of {key}.");
+    eprintln!();
+
+    loop {
+        if let Some(output) = watcher.poll() {
+            print!("{output}");
+        }
+        thread::sleep(interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_run() {
+        let report = |answer: &str, parse: u64, solve: u64| RunReport {
+            key: Key::new(2024, 1),
+            part: 1,
+            path: "in.txt".to_string(),
+            result: Ok(answer.to_string()),
+            parse_elapsed: Some(Duration::from_millis(parse)),
+            elapsed: Duration::from_millis(solve),
+        };
+
+        let first = report("11", 2, 5);
+        assert_eq!(
+            describe_run(1, None, &first),
+            "Run 1: 11  parse 2.00 ms  solve 5.00 ms"
+        );
+        assert_eq!(
+            describe_run(2, Some(&first), &report("12", 3, 4)),
+            "Run 2: 12 (was 11)  parse 3.00 ms (+1.00 ms)  solve 4.00 ms (-1.00 ms)"
+        );
+        assert!(describe_run(2, Some(&first), &first).starts_with("Run 2: 11 (unchanged)"));
+    }
+
+    #[test]
+    fn test_watcher() {
+        let path = std::env::temp_dir().join("aoc_test_watcher.txt");
+        let path_str = path.to_str().unwrap();
+        fs::write(&path, "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n").unwrap();
+
+        let options = WatchOptions {
+            examples: true,
+            ..WatchOptions::default()
+        };
+        let mut watcher = Watcher::new(Key::new(2024, 1), 1, path_str, options.clone()).unwrap();
+        let output = watcher.poll().unwrap();
+        assert!(output.starts_with("Run 1: 11  parse"), "{output}");
+        assert!(output.contains("example 1: pass"), "{output}");
+        assert_eq!(watcher.poll(), None);
+
+        fs::write(&path, "1   4\n4   3\n").unwrap();
+        let output = watcher.poll().unwrap();
+        assert!(output.starts_with("Run 2: 2 (was 11)"), "{output}");
+
+        fs::remove_file(&path).unwrap();
+        let output = watcher.poll().unwrap();
+        assert!(
+            output.starts_with("Run 3: missing input (was 2)"),
+            "{output}"
+        );
+
+        assert!(Watcher::new(Key::new(2024, 1), 1, STDIN_PATH, options.clone()).is_err());
+        assert!(Watcher::new(Key::new(2024, 22), 1, path_str, options).is_err());
+    }
+}
@@ -1,66 +1,407 @@
-use aoc::run_w_args;
+use aoc::answers::{verify, verify_examples, Answers, Status, Verification, DEFAULT_ANSWERS_PATH};
+use aoc::bench::{bench, BenchOptions};
+use aoc::common::{default_input_path, read_lines, run_all, run_parts, RunOptions, RunReport};
+use aoc::days::registry;
+use aoc::solution::{Context, Key};
+use aoc::{run_on_str, run_w_args, RunError};
 use paste::paste;
+use std::fs;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+fn answers() -> &'static Answers {
+    static ANSWERS: OnceLock<Answers> = OnceLock::new();
+    ANSWERS.get_or_init(|| Answers::load(DEFAULT_ANSWERS_PATH).unwrap())
+}
+
+fn assert_run(year: u16, day: u8, part: u8) {
+    let key = Key::new(year, day);
+    let path = default_input_path(key);
+    let expected = answers()
+        .expected(key, part, &path)
+        .unwrap_or_else(|| panic!("no answer to part {part} of {key} in answers.toml"));
 
-fn assert_run(day: u8, part: u8, expected: &str) {
     let args = vec![
         "program".into(),
         day.to_string(),
         part.to_string(),
-        format!("./inputs/input{day}.txt"),
+        path,
+        "--year".into(),
+        year.to_string(),
     ];
-    let result = run_w_args(&args);
+    let result = run_w_args(&args).unwrap();
     assert_eq!(result, expected);
 }
 
+#[test]
+fn test_registry_covers_calendar() {
+    let keys: Vec<Key> = registry().keys().filter(|key| key.year == 2024).collect();
+    let expected: Vec<Key> = (1..=25).map(|day| Key::new(2024, day)).collect();
+    assert_eq!(keys, expected);
+
+    for solution in registry().iter() {
+        assert!(!solution.name().is_empty());
+    }
+}
+
+#[test]
+fn test_registry_run_by_key() {
+    let solution = registry().get(Key::new(2024, 1)).unwrap();
+    let mut lines = read_lines("inputs/2024/day01.txt").unwrap();
+    assert_eq!(
+        solution.run(1, &mut lines, &Context::default()),
+        Some(Ok("2176849".to_string()))
+    );
+    assert_eq!(solution.run(3, &mut lines, &Context::default()), None);
+}
+
+fn run_err(args: &[&str]) -> RunError {
+    let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+    run_w_args(&args).unwrap_err()
+}
+
+#[test]
+fn test_run_errors() {
+    assert!(matches!(run_err(&["program", "1"]), RunError::Usage(_)));
+    assert!(matches!(run_err(&[]), RunError::Usage(_)));
+    assert!(matches!(run_err(&["--example"]), RunError::Usage(_)));
+    assert!(matches!(
+        run_err(&["program", "0", "1"]),
+        RunError::BadDay(_)
+    ));
+    assert!(matches!(
+        run_err(&["program", "26", "1"]),
+        RunError::BadDay(_)
+    ));
+    assert!(matches!(
+        run_err(&["program", "x", "1"]),
+        RunError::BadDay(_)
+    ));
+    assert!(matches!(
+        run_err(&["program", "1", "3"]),
+        RunError::BadPart(_)
+    ));
+    assert!(matches!(
+        run_err(&["program", "1", "1", "--year", "2015"]),
+        RunError::BadYear(_)
+    ));
+    assert!(matches!(
+        run_err(&["program", "1", "1", "--year", "x"]),
+        RunError::BadYear(_)
+    ));
+    assert!(matches!(
+        run_err(&["program", "1", "1", "inputs/2024/does_not_exist.txt"]),
+        RunError::MissingInput { .. }
+    ));
+    assert!(matches!(
+        run_err(&["program", "22", "1", "inputs/2024/day01.txt"]),
+        RunError::Unimplemented { part: 1, .. }
+    ));
+    assert!(matches!(
+        run_err(&["program", "21", "2", "inputs/2024/day21.txt"]),
+        RunError::Unimplemented { part: 2, .. }
+    ));
+}
+
+#[test]
+fn test_solver_failure() {
+    // A maze whose start and end are walled off from each other.
+    let path = std::env::temp_dir().join("aoc_test_solver_failure.txt");
+    std::fs::write(&path, "#####\n#..E#\n#####\n#S..#\n#####\n").unwrap();
+
+    let args = ["program", "16", "1", path.to_str().unwrap()];
+    let err = run_err(&args);
+    assert!(matches!(err, RunError::SolverFailed { part: 1, .. }));
+    assert_eq!(err.exit_code(), 1);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_params_not_fitting_input() {
+    for (day, part, name, value) in [
+        (14, 1, "height", "0"),
+        (14, 2, "width", "5"),
+        (18, 1, "size", "5"),
+        (18, 2, "bytes", "99999"),
+        (18, 1, "bytes", "3450"),
+    ] {
+        let options = RunOptions {
+            params: vec![(name.to_string(), value.to_string())],
+            ..RunOptions::default()
+        };
+        let key = Key::new(2024, day);
+        let reports = run_parts(key, &[part], &default_input_path(key), &options);
+        assert!(
+            matches!(reports[0].result, Err(RunError::SolverFailed { .. })),
+            "{key} part {part} with {name}={value}: {:?}",
+            reports[0].result
+        );
+    }
+}
+
+#[test]
+fn test_run_both_parts() {
+    let reports = run_parts(
+        Key::new(2024, 1),
+        &[1, 2],
+        "inputs/2024/day01.txt",
+        &RunOptions::default(),
+    );
+    assert_eq!(reports[0].result.as_deref().ok(), Some("2176849"));
+    assert_eq!(reports[1].result.as_deref().ok(), Some("23384288"));
+    assert!(reports[0].parse_elapsed.is_some());
+    assert!(reports[1].parse_elapsed.is_none());
+
+    let args: Vec<String> = ["program", "1", "both"].map(String::from).to_vec();
+    assert_eq!(run_w_args(&args).unwrap(), "2176849\n23384288");
+
+    let reports = run_parts(
+        Key::new(2024, 1),
+        &[1, 2],
+        "inputs/2024/does_not_exist.txt",
+        &RunOptions::default(),
+    );
+    for report in &reports {
+        assert!(matches!(report.result, Err(RunError::MissingInput { .. })));
+    }
+
+    let reports = run_parts(
+        Key::new(2024, 21),
+        &[2, 1],
+        "inputs/2024/day21.txt",
+        &RunOptions::default(),
+    );
+    assert!(matches!(
+        reports[0].result,
+        Err(RunError::Unimplemented { part: 2, .. })
+    ));
+    assert!(reports[0].parse_elapsed.is_none());
+    assert!(reports[1].parse_elapsed.is_some());
+}
+
+#[test]
+fn test_run_parts_catches_panics() {
+    let path = std::env::temp_dir().join("aoc_test_panicking_input.txt");
+    fs::write(&path, "1 2 x\n").unwrap();
+
+    let options = RunOptions {
+        timeout: Some(Duration::from_secs(10)),
+        ..RunOptions::default()
+    };
+    for options in [RunOptions::default(), options] {
+        let reports = run_parts(Key::new(2024, 2), &[1, 2], path.to_str().unwrap(), &options);
+        for report in &reports {
+            assert!(
+                matches!(report.result, Err(RunError::Panicked { .. })),
+                "{:?}",
+                report.result
+            );
+            assert!(report.status().starts_with("panicked: "));
+        }
+    }
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_run_all_marks_stubs() {
+    let reports = run_all(2024, 21..=25, 2, &RunOptions::default());
+    assert_eq!(reports.len(), 10);
+    assert_eq!(reports[0].result.as_deref().ok(), Some("105458"));
+    for report in &reports[1..] {
+        let solution = registry().get(report.key).unwrap();
+        if !solution.is_implemented(report.part) {
+            assert!(matches!(report.result, Err(RunError::Unimplemented { .. })));
+            assert!(report.is_success());
+        }
+    }
+}
+
+#[test]
+fn test_run_all_in_parallel() {
+    let sequential = run_all(2024, 1..=5, 1, &RunOptions::default());
+    let parallel = run_all(2024, 1..=5, 4, &RunOptions::default());
+
+    let outcome = |reports: &[RunReport]| -> Vec<_> {
+        reports
+            .iter()
+            .map(|report| {
+                (
+                    report.key,
+                    report.part,
+                    report.result.as_ref().ok().cloned(),
+                )
+            })
+            .collect()
+    };
+    assert_eq!(outcome(&sequential), outcome(&parallel));
+    assert_eq!(parallel.len(), 10);
+    assert!(parallel
+        .windows(2)
+        .all(|pair| { (pair[0].key.day, pair[0].part) < (pair[1].key.day, pair[1].part) }));
+
+    assert!(run_all(2024, 1..=2, 8, &RunOptions::default())
+        .iter()
+        .all(|report| report.result.is_ok()));
+}
+
+#[test]
+fn test_bench_is_deterministic() {
+    let options = BenchOptions {
+        iters: 3,
+        warmup: 1,
+        ..BenchOptions::default()
+    };
+    let report = bench(Key::new(2024, 1), 1, "inputs/2024/day01.txt", options).unwrap();
+    assert!(report.is_deterministic());
+    assert_eq!(report.answers, vec![("2176849".to_string(), 4)]);
+}
+
+#[test]
+fn test_bench_rejects_no_iters() {
+    let options = BenchOptions {
+        iters: 0,
+        ..BenchOptions::default()
+    };
+    assert!(matches!(
+        bench(Key::new(2024, 1), 1, "inputs/2024/day01.txt", options),
+        Err(RunError::BadArgument(_))
+    ));
+}
+
+#[test]
+fn test_bench_uses_params() {
+    let options = BenchOptions {
+        iters: 1,
+        warmup: 0,
+        params: vec![("gens".to_string(), "25".to_string())],
+    };
+    let report = bench(Key::new(2024, 11), 2, "inputs/2024/day11.txt", options).unwrap();
+    assert_eq!(report.answers, vec![("224529".to_string(), 1)]);
+}
+
+#[test]
+fn test_run_on_str() {
+    let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+    assert_eq!(run_on_str(1, 1, input).unwrap(), "11");
+    assert_eq!(run_on_str(1, 2, input).unwrap(), "31");
+    assert!(matches!(
+        run_on_str(22, 1, input),
+        Err(RunError::Unimplemented { .. })
+    ));
+}
+
+#[test]
+fn test_examples() {
+    let mut failures = Vec::new();
+    for solution in registry().iter() {
+        for part in [1, 2] {
+            if !solution.is_implemented(part) {
+                continue;
+            }
+            for verification in
+                verify_examples(solution.key(), part, &RunOptions::default()).unwrap()
+            {
+                if verification.status() != Status::Pass {
+                    failures.push(format!(
+                        "part {part} of {} on {}: expected {:?}, got {:?}",
+                        solution.key(),
+                        verification.report.path,
+                        verification.expected,
+                        verification.report.result,
+                    ));
+                }
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn test_verify() {
+    let answers = Answers::parse(
+        r#"
+        ["inputs/2024/day01.txt"]
+        day = 1
+        part1 = "2176849"
+        part2 = "0"
+
+        ["inputs/2024/day21.txt"]
+        day = 21
+        part1 = "105458"
+        "#,
+    )
+    .unwrap();
+
+    let statuses: Vec<Status> = verify(&answers, &RunOptions::default())
+        .iter()
+        .map(Verification::status)
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![Status::Pass, Status::Fail, Status::Pass, Status::Unknown]
+    );
+}
+
 macro_rules! test_day {
-    ($day:literal, $part:literal, $expected:literal) => {
+    ($year:literal, $day:literal, $part:literal) => {
+        paste! {
+            #[test]
+            fn [<test_ $year _day_ $day _part_ $part>]() {
+                assert_run($year, $day, $part);
+            }
+        }
+    };
+    ($year:literal, $day:literal, $part:literal, pending) => {
         paste! {
             #[test]
-            fn [<test_day_ $day _part_ $part>]() {
-                assert_run($day, $part, $expected);
+            #[ignore = "no known answer yet"]
+            fn [<test_ $year _day_ $day _part_ $part>]() {
+                assert_run($year, $day, $part);
             }
         }
     };
 }
 
-test_day!(1, 1, "2176849");
-test_day!(1, 2, "23384288");
-test_day!(2, 1, "402");
-test_day!(2, 2, "455");
-test_day!(3, 1, "171183089");
-test_day!(3, 2, "63866497");
-test_day!(4, 1, "2462");
-test_day!(4, 2, "1877");
-test_day!(5, 1, "5762");
-test_day!(5, 2, "4130");
-test_day!(6, 1, "4903");
-test_day!(6, 2, "1911");
-test_day!(7, 1, "1289579105366");
-test_day!(7, 2, "92148721834692");
-test_day!(8, 1, "285");
-test_day!(8, 2, "944");
-test_day!(9, 1, "6349606724455");
-test_day!(9, 2, "6376648986651");
-test_day!(10, 1, "719");
-test_day!(10, 2, "1530");
-test_day!(11, 1, "224529");
-test_day!(11, 2, "266820198587914");
-test_day!(12, 1, "1486324");
-test_day!(12, 2, "898684");
-test_day!(13, 1, "26299");
-test_day!(13, 2, "107824497933339");
-test_day!(14, 1, "228457125");
-test_day!(14, 2, "6493");
-test_day!(15, 1, "1438161");
-test_day!(15, 2, "1437981");
-test_day!(16, 1, "95444");
-test_day!(16, 2, "513");
-test_day!(17, 1, "6,0,6,3,0,2,3,1,6");
-test_day!(17, 2, "236539226447469");
-test_day!(18, 1, "252");
-test_day!(18, 2, "5,60");
-test_day!(19, 1, "242");
-test_day!(19, 2, "595975512785325");
-test_day!(20, 1, "1351");
-test_day!(20, 2, "966130");
-test_day!(21, 1, "105458");
+test_day!(2024, 1, 1);
+test_day!(2024, 1, 2);
+test_day!(2024, 2, 1);
+test_day!(2024, 2, 2);
+test_day!(2024, 3, 1);
+test_day!(2024, 3, 2);
+test_day!(2024, 4, 1);
+test_day!(2024, 4, 2);
+test_day!(2024, 5, 1);
+test_day!(2024, 5, 2);
+test_day!(2024, 6, 1);
+test_day!(2024, 6, 2);
+test_day!(2024, 7, 1);
+test_day!(2024, 7, 2);
+test_day!(2024, 8, 1);
+test_day!(2024, 8, 2);
+test_day!(2024, 9, 1);
+test_day!(2024, 9, 2);
+test_day!(2024, 10, 1);
+test_day!(2024, 10, 2);
+test_day!(2024, 11, 1);
+test_day!(2024, 11, 2);
+test_day!(2024, 12, 1);
+test_day!(2024, 12, 2);
+test_day!(2024, 13, 1);
+test_day!(2024, 13, 2);
+test_day!(2024, 14, 1);
+test_day!(2024, 14, 2);
+test_day!(2024, 15, 1);
+test_day!(2024, 15, 2);
+test_day!(2024, 16, 1);
+test_day!(2024, 16, 2);
+test_day!(2024, 17, 1);
+test_day!(2024, 17, 2);
+test_day!(2024, 18, 1);
+test_day!(2024, 18, 2);
+test_day!(2024, 19, 1);
+test_day!(2024, 19, 2);
+test_day!(2024, 20, 1);
+test_day!(2024, 20, 2);
+test_day!(2024, 21, 1);